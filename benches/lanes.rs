@@ -0,0 +1,52 @@
+// Benchmark: Argon2id round with the key derivation parameters
+// single threaded argon2 crate vs. the multi threaded lanes module
+// run with: cargo bench --bench lanes
+//
+#[path = "../src/lanes.rs"]
+mod lanes;
+
+use argon2::{Algorithm, Argon2, Block, Params, Version};
+use std::time::{Duration, Instant};
+
+// same values as A_MEMORY, A_TIME and A_PARALLELISM in constants.rs
+const A_MEMORY: u32 = 2 * 1024 * 1024;
+const A_TIME: u32 = 5;
+const A_PARALLELISM: u32 = 4;
+
+fn main() {
+    let params = Params::new(A_MEMORY, A_TIME, A_PARALLELISM, Some(64)).expect("Failed to create Argon2 parameters");
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone());
+    let mut blocks = vec![Block::new(); params.block_count()];
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    println!("Argon2id round: {} MiB, {} passes, {} lanes, {} CPU threads available", A_MEMORY / 1024, A_TIME, A_PARALLELISM, threads);
+    let compression = lanes::compressors().last().map_or("scalar", |&(name, _)| name);
+    println!("compression of the multi threaded round: {}\n", compression);
+
+    // one round with the argon2 crate (lanes filled on a single thread)
+    let mut single = [0u8; 64];
+    let single_time = time(|| {
+        argon2
+            .hash_password_into_with_memory(b"benchmark password", b"benchmark salt", &mut single, &mut blocks)
+            .expect("Failed to hash password");
+    });
+    println!("single thread : {:>8.2} seconds", single_time.as_secs_f64());
+
+    // one round with every lane on its own thread
+    let mut parallel = [0u8; 64];
+    let parallel_time = time(|| {
+        lanes::hash_password_parallel(&params, b"benchmark password", b"benchmark salt", &mut parallel, &mut blocks)
+            .expect("Failed to hash password");
+    });
+    println!("multi thread  : {:>8.2} seconds", parallel_time.as_secs_f64());
+
+    // the keys must be byte identical
+    assert_eq!(single, parallel, "multi threaded Argon2 produced a different key");
+    println!("\nspeed-up      : {:>8.2}x (keys are identical)", single_time.as_secs_f64() / parallel_time.as_secs_f64());
+}
+
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
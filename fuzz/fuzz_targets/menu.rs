@@ -0,0 +1,24 @@
+// Fuzz the menu and wallet size parsers: any input gives a valid choice or a ParseError
+//
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrambler::constants::*;
+use scrambler::parse::{parsechoice, parsewalletsize};
+
+fuzz_target!(|data: &[u8]| {
+    // the first byte is the number of choices
+    let Some((&choices, input)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+
+    if let Ok(choice) = parsechoice(input, choices as usize) {
+        assert!(choice < choices as usize);
+    }
+    if let Ok(size) = parsewalletsize(input) {
+        assert!((MIN_WORDS..=MAX_WORDS).contains(&size));
+    }
+});
@@ -0,0 +1,43 @@
+// Fuzz the mnemonic parser: the words it finds are always real words of the wordlist
+// (starting with what was typed, without accents, or with that complete romanization)
+// and the words it reports as unknown are really not in it
+//
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrambler::constants::*;
+use scrambler::parse::{foldword, parsewords, resolveword, tokenize, ParseError};
+use scrambler::romanize::readingwords;
+
+fuzz_target!(|data: &[u8]| {
+    // the first byte picks the wordlist
+    let Some((&lang, text)) = data.split_first() else {
+        return;
+    };
+    let lang = lang as usize % WORDS.len();
+    let Ok(text) = std::str::from_utf8(text) else {
+        return;
+    };
+
+    let tokens = tokenize(text);
+    match parsewords(text, lang) {
+        Ok(words) => {
+            assert_eq!(words.len(), tokens.len());
+            assert!(words
+                .iter()
+                .zip(&tokens)
+                .all(|(&word, token)| foldword(WORDS[lang][word]).starts_with(&foldword(&token.word))
+                    || readingwords(&token.word, lang) == [word]));
+        }
+        Err(ParseError::UnknownWords(found, unknown)) => {
+            assert_eq!(found, lang);
+            assert!(!unknown.is_empty());
+            for (position, token) in &unknown {
+                assert!(*position >= 1 && *position <= tokens.len());
+                assert_eq!(&tokens[position - 1], token);
+                assert!(resolveword(&token.word, lang).is_none());
+            }
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+    }
+});
@@ -0,0 +1,25 @@
+// Fuzz the word suggestions: any typed text gives at most 3 different words from the wordlist
+//
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrambler::constants::*;
+use scrambler::utils::find_suggestions;
+
+fuzz_target!(|data: &[u8]| {
+    // the first byte picks the wordlist
+    let Some((&lang, word)) = data.split_first() else {
+        return;
+    };
+    let lang = lang as usize % WORDS.len();
+    let Ok(word) = std::str::from_utf8(word) else {
+        return;
+    };
+
+    let suggestions = find_suggestions(word, lang);
+    assert!(suggestions.len() <= 3);
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        assert!(!suggestion.is_empty() && WORDS[lang].contains(&suggestion.as_str()));
+        assert!(!suggestions[..i].contains(suggestion));
+    }
+});
@@ -0,0 +1,23 @@
+// Fuzz the wallet file reader: any file either parses into valid words or gives a ParseError
+//
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrambler::constants::*;
+use scrambler::parse::parsewalletfile;
+
+fuzz_target!(|data: &[u8]| {
+    // files that are not UTF-8 are rejected when they are read
+    let Ok(contents) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(found) = parsewalletfile(contents) {
+        assert!(!found.is_empty());
+        assert!(found.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for (lang, words) in found {
+            assert!((MIN_WORDS..=MAX_WORDS + SALT_WORDS).contains(&words.len()));
+            assert!(words.iter().all(|&word| !WORDS[lang][word].is_empty()));
+        }
+    }
+});
@@ -0,0 +1,21 @@
+// Fuzz the JSON wallet file decoder: any file either decodes into valid words or gives a ParseError
+// and a decoded file encodes back to a file that decodes the same
+//
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrambler::constants::*;
+use scrambler::walletfile::{decodewallet, encodewallet};
+
+fuzz_target!(|data: &[u8]| {
+    // files that are not UTF-8 are rejected when they are read
+    let Ok(contents) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok((lang, words, info)) = decodewallet(contents) {
+        assert!((MIN_WORDS..=MAX_WORDS).contains(&(words.len() - info.saltwords)));
+        assert!(words.iter().all(|&word| !WORDS[lang][word].is_empty()));
+        assert_eq!(decodewallet(&encodewallet(lang, &words, &info)), Ok((lang, words, info)));
+    }
+});
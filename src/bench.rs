@@ -0,0 +1,104 @@
+// Module: bench
+// Calibrate the key derivation on this machine: time one round with the real parameters,
+// predict the total derivation time and how long it would take an attacker to guess the password
+//
+use crate::constants::*;
+use crate::crypto::*;
+use crate::error::ScramblerError;
+use crate::utils::*;
+
+// password entropies (in bits) shown in the crack time table
+const ENTROPIES: [u32; 6] = [30, 40, 50, 60, 70, 80];
+
+// attackers, as a number of machines as fast as this one
+const ATTACKERS: [(f64, &str); 3] = [
+    (1.0, "1 machine"),
+    (1e3, "1,000 machines"),
+    (1e6, "1,000,000 machines"),
+];
+
+// Run the key derivation benchmark
+pub fn bench() -> Result<(), ScramblerError> {
+    println!("\nCatsec wallet word scrambler - key derivation benchmark\n");
+
+    // the benchmark uses the real parameters, so it needs the same memory as the key derivation
+    let needed = kdfmemory(KDF_CASCADE, &FULL_COST);
+    if let Some(available) = availablememory() {
+        if available < needed + MEMORY_MARGIN {
+            return Err(ScramblerError::Memory { needed: needed + MEMORY_MARGIN, available });
+        }
+    }
+
+    // time one Argon2 round and one scrypt run with the real parameters
+    println!(
+        "Timing one Argon2id round ({} MiB, {} passes, {} lanes)...",
+        A_MEMORY / 1024,
+        A_TIME,
+        A_PARALLELISM
+    );
+    let round = timeargon2(A_MEMORY, A_TIME)?.as_secs_f64();
+    println!("One Argon2id round: {:.2} seconds\n", round);
+
+    println!("Timing one scrypt run (cascade scheme)...");
+    let scrypt = timescrypt()?.as_secs_f64();
+    println!("One scrypt run: {:.2} seconds", scrypt);
+
+    for (kdf, name) in KDF.iter().enumerate() {
+        // predicted time of the full key derivation
+        let mut derivation = round * ITERATIONS as f64;
+        if kdf == KDF_CASCADE {
+            derivation += scrypt;
+        }
+
+        // v1 only uses the output of the last round, so testing a password only costs one round
+        let guess = if kdf == KDF_V1 { round } else { derivation };
+
+        println!("\n{}", name);
+        println!("  key derivation time:     {}", humantime(derivation));
+        println!("  attacker cost per guess: {}\n", humantime(guess));
+
+        // average time to guess the password (half of all the possible passwords)
+        print!("  {:>13}", "entropy");
+        for (_, name) in ATTACKERS {
+            print!(" | {:>20}", name);
+        }
+        println!();
+        for bits in ENTROPIES {
+            print!("  {:>8} bits", bits);
+            for (machines, _) in ATTACKERS {
+                print!(" | {:>20}", humantime(2f64.powi(bits as i32 - 1) * guess / machines));
+            }
+            println!();
+        }
+    }
+
+    println!(
+        "\nA random 12 characters password (upper, lower, numbers & special chars) has about 78 bits of entropy,\n\
+        a password made of words or personal details has a lot less.\n\
+        Attackers may have faster hardware, but memory-hard functions limit their advantage."
+    );
+    Ok(())
+}
+
+// Format a number of seconds in a human readable way
+fn humantime(seconds: f64) -> String {
+    const UNITS: [(f64, &str); 5] = [
+        (365.25 * 24.0 * 3600.0, "years"),
+        (24.0 * 3600.0, "days"),
+        (3600.0, "hours"),
+        (60.0, "minutes"),
+        (1.0, "seconds"),
+    ];
+
+    for (size, name) in UNITS {
+        if seconds >= size {
+            let value = seconds / size;
+            return if value >= 1e6 {
+                format!("{:.1e} {}", value, name)
+            } else {
+                format!("{:.1} {}", value, name)
+            };
+        }
+    }
+    format!("{:.2} seconds", seconds)
+}
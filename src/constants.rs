@@ -12,12 +12,45 @@ pub const A_PARALLELISM: u32 = 4;
 pub const ITERATIONS: usize = 10;
 pub const BAR_SIZE: usize = 40;
 
+// memory kept free for the rest of the program when checking the key derivation fits
+pub const MEMORY_MARGIN: u64 = 128 * 1024 * 1024;
+
+// key derivation schemes, backups can only be recovered with the scheme used to create them
+// so never change or reorder them (new schemes are added at the end)
+pub const KDF_V1: usize = 0;
+pub const KDF_CASCADE: usize = 2;
+pub const KDF: [&str; 3] = [
+    "v1 - original (use it to recover backups made with older versions)",
+    "v2 - chained rounds (recommended for new backups)",
+    "v3 - cascade of chained Argon2id and scrypt (hedged against a broken primitive, slower)",
+];
+// the names of the schemes in wallet files, never change them
+pub const KDF_ID: [&str; 3] = ["v1", "v2", "v3"];
+
+// scrypt parameters of the cascade scheme (128 * r * 2^log_n = 1 GiB), do not change
+pub const S_LOG_N: u8 = 20;
+pub const S_R: u32 = 8;
+pub const S_P: u32 = 1;
+
+// exit codes (0 is success)
+pub const EXIT_INPUT: i32 = 1; // bad input
+pub const EXIT_IO: i32 = 2; // reading or writing a file or the terminal failed
+pub const EXIT_KDF: i32 = 3; // the key derivation failed or can't be trusted
+pub const EXIT_ABORT: i32 = 4; // the user chose to stop
+
+// wallet files: the format name and the version this program writes (it reads all the older ones)
+pub const WALLET_FORMAT: &str = "catsec-scrambler-wallet";
+pub const WALLET_VERSION: u32 = 1;
+
 // you may change this on your own risk
 pub const MAX_WORDS: usize = 33;
+pub const MIN_WORDS: usize = 12;
+pub const SALT_WORDS: usize = 2;
 pub const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 pub const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
 pub const NUMBERS: &str = "0123456789";
 pub const SPECIAL: &str = "!@#$%^&*()-_=+[]{}|;:'\",.<>?/";
+pub const LABEL_SPECIAL: &str = "-_. ";
 
 // if wordlists are added you can add them here (but don't overide)
 pub const LANG: [&str; 11] = [
@@ -32,4 +65,27 @@ pub const LANG: [&str; 11] = [
     "korean (BIP 39, 2048 words)",
     "Chinese simplified (BIP 39, 2048 symbols)",
     "chinese traditional (BIP 39, 2048 symblos)",
-];
\ No newline at end of file
+];
+
+// the names of the wordlists in wallet files, never change them
+pub const LANG_ID: [&str; 11] = [
+    "slip39",
+    "english",
+    "czech",
+    "french",
+    "italian",
+    "portuguese",
+    "spanish",
+    "japanese",
+    "korean",
+    "chinese_simplified",
+    "chinese_traditional",
+];
+
+// the wordlists that can also be typed in latin letters (romaji, revised romanization, pinyin)
+pub const LANG_JAPANESE: usize = 7;
+pub const LANG_KOREAN: usize = 8;
+pub const LANG_CHINESE_SIMPLIFIED: usize = 9;
+pub const LANG_CHINESE_TRADITIONAL: usize = 10;
+// the most words offered for the start of a reading, more letters are asked for above it
+pub const MAX_READING_CHOICES: usize = 10;
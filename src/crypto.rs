@@ -3,12 +3,17 @@
 // and you will not be able to recover your wallet
 //
 use crate::constants::*;
+use crate::error::ScramblerError;
+use crate::lanes::hash_password_parallel;
+use crate::memory::ArgonMemory;
+use crate::terminal::Terminal;
+use crate::utils::getwordlistbitsize;
 
-use argon2::{Argon2, Params};
+use argon2::Params;
+use scrypt::{scrypt, Params as ScryptParams};
 use sha3::{Digest, Sha3_512};
-use std::io::{self, Write};
-use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
 
 // Hash the data using SHA3-512 for the specified number of iterations
 pub fn sha3(data: &[u8], iterations: u32) -> Vec<u8> {
@@ -21,61 +26,282 @@ pub fn sha3(data: &[u8], iterations: u32) -> Vec<u8> {
     hash
 }
 
-// Derive a secret key from the password using Argon2
-pub fn derive_key(password: Vec<u8>) -> [u8; 64] {
-    println!("\nDeriving secret key, this WILL take a while (have some tea and relax)\n");
+// Build the data the salts are derived from: the password, optionally bound to a wallet label
+// and random salt words, without both the original salts are kept so existing backups stay recoverable
+pub fn saltdata(password: &[u8], label: &[u8], salt: &[usize]) -> Vec<u8> {
+    let mut data = password.to_vec();
+    if !label.is_empty() || !salt.is_empty() {
+        // passwords and labels are printable ascii, so a zero byte cleanly separates the parts
+        data.push(0);
+        data.extend_from_slice(label);
+    }
+    if !salt.is_empty() {
+        // the salt words are added as their 2 byte word indexes
+        data.push(0);
+        for &word in salt {
+            data.extend_from_slice(&(word as u16).to_be_bytes());
+        }
+    }
+    data
+}
+
+// Generate random salt words, stored unscrambled next to the scrambled words
+pub fn randomsalt(lang: usize) -> Result<Vec<usize>, ScramblerError> {
+    // word lists have a power of 2 number of words, so masking the random value keeps it uniform
+    let mask = (1 << getwordlistbitsize(lang)) - 1;
+
+    let mut random = [0u8; 2 * SALT_WORDS];
+    getrandom::getrandom(&mut random)
+        .map_err(|e| ScramblerError::Kdf(format!("could not generate the random salt words: {}", e)))?;
+
+    Ok(random
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]) as usize & mask)
+        .collect())
+}
+
+// Cost parameters of the key derivation
+pub struct KdfCost {
+    pub name: &'static str, // recorded in wallet files
+    pub memory: u32,       // Argon2 memory in KiB
+    pub time: u32,         // Argon2 passes
+    pub parallelism: u32,  // Argon2 lanes
+    pub iterations: usize, // Argon2 rounds
+    pub scrypt_log_n: u8,  // scrypt cost of the cascade scheme
+}
+
+// the real cost, backups can only be recovered with it
+pub const FULL_COST: KdfCost = KdfCost {
+    name: "full",
+    memory: A_MEMORY,
+    time: A_TIME,
+    parallelism: A_PARALLELISM,
+    iterations: ITERATIONS,
+    scrypt_log_n: S_LOG_N,
+};
+
+// a tiny cost to check the key derivation logic quickly, NEVER use it for a backup
+pub const TEST_COST: KdfCost = KdfCost {
+    name: "test",
+    memory: 256,
+    time: 2,
+    parallelism: A_PARALLELISM,
+    iterations: ITERATIONS,
+    scrypt_log_n: 10,
+};
+
+// Derive a secret key from the password (and optional wallet label and salt words) using Argon2
+// v1: every round hashes the password with a fresh salt, only the last round counts
+// v2: every round's output is chained into the next round's salt, so all rounds count
+// v3: cascade, the v2 output and an independent scrypt output are hashed together
+pub fn derive_key(
+    term: &mut dyn Terminal,
+    password: &[u8],
+    label: &[u8],
+    salt: &[usize],
+    kdf: usize,
+    cost: &KdfCost,
+) -> Result<Zeroizing<[u8; 64]>, ScramblerError> {
+    term.println("\nDeriving secret key, this WILL take a while (have some tea and relax)\n");
+    derive_key_with(password, label, salt, kdf, cost, Some(term))
+}
 
-    // Create Argon2 parameters
-    let params = Params::new(A_MEMORY, A_TIME, A_PARALLELISM, Some(64))
-        .expect("Failed to create Argon2 parameters");
+// Derive a secret key with the given cost, showing the progress on the terminal if there is one
+pub fn derive_key_with(
+    password: &[u8],
+    label: &[u8],
+    salt: &[usize],
+    kdf: usize,
+    cost: &KdfCost,
+    mut term: Option<&mut dyn Terminal>,
+) -> Result<Zeroizing<[u8; 64]>, ScramblerError> {
+    // the salts are derived from the password, the wallet label and the salt words
+    // (the salt data and the key are wiped when dropped, also when returning an error)
+    let data = Zeroizing::new(saltdata(password, label, salt));
 
-    // Create an Argon2 instance with Argon2id and version 0x13 (the latest version at the time of writing)
-    let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    // Create Argon2 parameters (Argon2id version 0x13, the latest version at the time of writing)
+    let params = Params::new(cost.memory, cost.time, cost.parallelism, Some(64))
+        .map_err(|e| ScramblerError::Kdf(format!("invalid Argon2 parameters: {}", e)))?;
 
     // Create a 64-byte buffer to store the secret key
-    let mut secret_key = [0u8; 64];
+    let mut secret_key = Zeroizing::new([0u8; 64]);
+
+    // Allocate the Argon2 working memory ourselves, so it can be locked and wiped
+    let mut memory = ArgonMemory::new(params.block_count());
+    if let Some(term) = term.as_deref_mut() {
+        if !memory.locked() {
+            term.println("Note: could not lock the key derivation memory in RAM, it might be swapped to disk\n");
+        }
+    }
 
     // Track the start time
     let start_time = Instant::now();
-    
-    print!("[{}] 0% (Time left: calculating)", " ".repeat(BAR_SIZE));
-    io::stdout().flush().expect("Failed to flush stdout");
-    
+
+    if let Some(term) = term.as_deref_mut() {
+        term.print(&format!("[{}] 0% (Time left: calculating)", " ".repeat(BAR_SIZE)));
+    }
+
     // Iterate for the specified number of iterations
-    for i in 1..=ITERATIONS {
-        // Create a new salt for each iteration by hashing the password and the iteration number
+    for i in 1..=cost.iterations {
+        // Create a new salt for each iteration by hashing the password, label and the iteration number
         let counter: u32 = i as u32 + 580;
-        let salt = sha3(&password, counter);
+        let mut salt = Zeroizing::new(sha3(&data, counter));
 
-        // Hash the password into the secret key using Argon2
-        if let Err(e) = argon2.hash_password_into(&password, &salt, &mut secret_key) {
-            eprintln!("Error hashing password: {}", e);
-            process::exit(1);
+        // v2 and later: chain the output of the previous round into the salt
+        if kdf != KDF_V1 {
+            salt.extend_from_slice(&*secret_key);
+            salt = Zeroizing::new(sha3(&salt, 1));
         }
 
+        // Hash the password into the secret key using Argon2, every lane on its own thread
+        let result = hash_password_parallel(&params, password, &salt, &mut *secret_key, memory.blocks());
+
+        // wipe the working memory after every round (and before returning an error)
+        memory.wipe();
+        result.map_err(|e| ScramblerError::Kdf(format!("Argon2 failed: {}", e)))?;
+
+        let Some(term) = term.as_deref_mut() else {
+            continue;
+        };
+
         // Calculate progress and estimated remaining time
         let elapsed_time = start_time.elapsed();
         let avg_time_per_step = elapsed_time / i as u32;
-        let remaining_steps = ITERATIONS - i;
+        let remaining_steps = cost.iterations - i;
         let estimated_remaining_time = format!("{} seconds", (avg_time_per_step * remaining_steps as u32).as_secs());
 
         // Generate progress bar
-        let progress_dots = "=".repeat((i * BAR_SIZE / ITERATIONS) as usize);
-        let remaining_dots = " ".repeat((BAR_SIZE - progress_dots.len()) as usize);
-        
+        let progress_dots = "=".repeat(i * BAR_SIZE / cost.iterations);
+        let remaining_dots = " ".repeat(BAR_SIZE - progress_dots.len());
+
         // Update the progress line
-        print!(
+        term.print(&format!(
             "\r[{}{}] {}% (Time left: {})",
             progress_dots,
             remaining_dots,
-            (i * 100) / ITERATIONS,
+            (i * 100) / cost.iterations,
             estimated_remaining_time
-        );
-        io::stdout().flush().expect("Failed to flush stdout");
+        ));
+    }
+
+    // free the Argon2 memory (it is wiped when dropped) before running scrypt
+    drop(memory);
+
+    // cascade: combine the Argon2id key with an independent scrypt key
+    if kdf == KDF_CASCADE {
+        if let Some(term) = term.as_deref_mut() {
+            term.println("\n\nRunning scrypt (cascade scheme)...");
+        }
+        cascade_key(password, &data, cost.scrypt_log_n, &mut secret_key)?;
     }
 
-    println!("\n\nKey derivation completed successfully.");
+    if let Some(term) = term {
+        term.println("\n\nKey derivation completed successfully.");
+    }
 
     // Return the secret key
-    secret_key
+    Ok(secret_key)
+}
+
+// Derive the key a second time (with a new memory allocation) and compare it to the first one
+// a mismatch means the hardware is faulty and the first key can't be trusted
+pub fn verify_key(
+    term: &mut dyn Terminal,
+    password: &[u8],
+    label: &[u8],
+    salt: &[usize],
+    kdf: usize,
+    cost: &KdfCost,
+    secret_key: &[u8; 64],
+) -> Result<(), ScramblerError> {
+    term.println("\nVerifying: deriving the secret key a second time");
+    let second_key = derive_key(term, password, label, salt, kdf, cost)?;
+    if *second_key != *secret_key {
+        return Err(ScramblerError::HardwareFault);
+    }
+
+    term.println("Verification passed, both key derivations produced the same key.");
+    Ok(())
+}
+
+// Mix an scrypt derived key into the Argon2id key, breaking one of the primitives is not enough
+// to recover the key, as it is the SHA3-512 hash of both outputs
+fn cascade_key(password: &[u8], data: &[u8], log_n: u8, secret_key: &mut [u8; 64]) -> Result<(), ScramblerError> {
+    // Create scrypt parameters
+    let params = ScryptParams::new(log_n, S_R, S_P, 64)
+        .map_err(|e| ScramblerError::Kdf(format!("invalid scrypt parameters: {}", e)))?;
+
+    // scrypt gets its own salt, counter 580 is never used by the Argon2 rounds
+    let salt = Zeroizing::new(sha3(data, 580));
+
+    // Derive the scrypt key (the intermediate keys are wiped when dropped)
+    let mut scrypt_key = Zeroizing::new([0u8; 64]);
+    scrypt(password, &salt, &params, &mut *scrypt_key)
+        .map_err(|e| ScramblerError::Kdf(format!("scrypt failed: {}", e)))?;
+
+    // hash both keys together into the final key
+    let mut combined = Zeroizing::new(secret_key.to_vec());
+    combined.extend_from_slice(&*scrypt_key);
+    let hash = Zeroizing::new(sha3(&combined, 1));
+    secret_key.copy_from_slice(&hash);
+    Ok(())
+}
+
+// Memory in bytes needed to derive the key with the given scheme
+// the cascade runs scrypt after the Argon2 memory was freed, so it needs the larger of both
+pub fn kdfmemory(kdf: usize, cost: &KdfCost) -> u64 {
+    let argon2 = cost.memory as u64 * 1024;
+    if kdf == KDF_CASCADE {
+        argon2.max(scryptmemory(cost.scrypt_log_n))
+    } else {
+        argon2
+    }
+}
+
+// Memory in bytes used by scrypt in the cascade scheme
+fn scryptmemory(log_n: u8) -> u64 {
+    128 * S_R as u64 * (1u64 << log_n)
+}
+
+// Roughly estimate the time it will take to derive the key, by timing a small Argon2 round
+// and scaling it to the real parameters (assumes the memory fits in RAM)
+pub fn estimatetime(kdf: usize, cost: &KdfCost) -> Result<Duration, ScramblerError> {
+    // a 32 MiB (at most) single pass round with the real number of lanes
+    let sample_memory: u32 = cost.memory.min(32 * 1024);
+    let sample = timeargon2(sample_memory, 1)?;
+
+    // scale to the real memory size, passes and rounds
+    let mut estimate = sample.mul_f64((cost.memory / sample_memory) as f64 * cost.time as f64 * cost.iterations as f64);
+
+    // scrypt takes roughly as long as 10 single threaded Argon2 passes over the same memory
+    if kdf == KDF_CASCADE {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get()).min(A_PARALLELISM as usize);
+        estimate += sample.mul_f64(10.0 * threads as f64 * (scryptmemory(cost.scrypt_log_n) / (sample_memory as u64 * 1024)) as f64);
+    }
+    Ok(estimate)
+}
+
+// Time a single Argon2 round with the given memory (in KiB) and passes, and the real number of lanes
+pub fn timeargon2(memory_kib: u32, passes: u32) -> Result<Duration, ScramblerError> {
+    let params = Params::new(memory_kib, passes, A_PARALLELISM, Some(64))
+        .map_err(|e| ScramblerError::Kdf(format!("invalid Argon2 parameters: {}", e)))?;
+    let mut memory = ArgonMemory::new(params.block_count());
+    let mut output = [0u8; 64];
+
+    let start_time = Instant::now();
+    hash_password_parallel(&params, b"benchmark", b"benchmark salt", &mut output, memory.blocks())
+        .map_err(|e| ScramblerError::Kdf(format!("Argon2 failed: {}", e)))?;
+    Ok(start_time.elapsed())
+}
+
+// Time a single scrypt run with the cascade parameters
+pub fn timescrypt() -> Result<Duration, ScramblerError> {
+    let params = ScryptParams::new(S_LOG_N, S_R, S_P, 64)
+        .map_err(|e| ScramblerError::Kdf(format!("invalid scrypt parameters: {}", e)))?;
+    let mut output = [0u8; 64];
+
+    let start_time = Instant::now();
+    scrypt(b"benchmark", b"benchmark salt", &params, &mut output)
+        .map_err(|e| ScramblerError::Kdf(format!("scrypt failed: {}", e)))?;
+    Ok(start_time.elapsed())
 }
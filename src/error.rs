@@ -0,0 +1,91 @@
+// Module: error
+// Every failure of the program, propagated up to main which shows the message and exits with the code
+// the secrets are wiped when they are dropped, so returning an error never leaves them in memory
+//
+use crate::constants::*;
+use crate::parse::ParseError;
+use crate::utils::gib;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum ScramblerError {
+    // bad input: words, wallet files or sizes that can't be used
+    Parse(ParseError),
+    Input(String),
+    // reading or writing a file or the terminal failed (what was being done, the error)
+    Io(String, io::Error),
+    // the key derivation failed
+    Kdf(String),
+    // not enough memory to derive the key (bytes)
+    Memory { needed: u64, available: u64 },
+    // the two key derivations gave different keys
+    HardwareFault,
+    // a self-test failed, this copy of the program can't be trusted
+    SelfTest(&'static str),
+    // the user chose to stop
+    Aborted,
+}
+
+impl ScramblerError {
+    // The exit code of the program for this error
+    pub fn code(&self) -> i32 {
+        match self {
+            ScramblerError::Parse(_) | ScramblerError::Input(_) => EXIT_INPUT,
+            ScramblerError::Io(..) => EXIT_IO,
+            ScramblerError::Kdf(_)
+            | ScramblerError::Memory { .. }
+            | ScramblerError::HardwareFault
+            | ScramblerError::SelfTest(_) => EXIT_KDF,
+            ScramblerError::Aborted => EXIT_ABORT,
+        }
+    }
+}
+
+impl fmt::Display for ScramblerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScramblerError::Parse(e) => write!(f, "{}", e),
+            ScramblerError::Input(message) => write!(f, "{}", message),
+            ScramblerError::Io(action, e) => write!(f, "Failed to {}: {}", action, e),
+            ScramblerError::Kdf(message) => write!(f, "Key derivation failed: {}", message),
+            ScramblerError::Memory { needed, available } => write!(
+                f,
+                "Not enough memory for the key derivation:\n\
+                \n  needed:    {}\n  available: {}\n\n\
+                Running it anyway would make the system swap heavily or kill the program.\n\
+                Close other programs or use a machine with more memory.\n\
+                The parameters can't be lowered, a backup can only be recovered with the exact same ones.",
+                gib(*needed),
+                gib(*available)
+            ),
+            ScramblerError::HardwareFault => write!(
+                f,
+                "************************************************************************\n\
+                *              ERROR: HARDWARE FAULT DETECTED, ABORTING                *\n\
+                *                                                                      *\n\
+                * Deriving the key twice from the same password gave different keys.   *\n\
+                * This machine's memory or CPU is unreliable, a backup made with it    *\n\
+                * might never be recoverable.  Nothing was scrambled or saved.         *\n\
+                * Use a different machine (and consider testing this one's RAM).       *\n\
+                ************************************************************************"
+            ),
+            ScramblerError::SelfTest(name) => write!(
+                f,
+                "The {} self-test failed, this copy of the program is broken.\n\
+                It might be miscompiled, corrupted or tampered with. DO NOT use it,\n\
+                get a fresh copy from a trusted source and verify its signature.",
+                name
+            ),
+            ScramblerError::Aborted => write!(f, "Exiting..."),
+        }
+    }
+}
+
+impl std::error::Error for ScramblerError {}
+
+impl From<ParseError> for ScramblerError {
+    fn from(e: ParseError) -> Self {
+        ScramblerError::Parse(e)
+    }
+}
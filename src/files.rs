@@ -0,0 +1,240 @@
+// Module: files
+// Writing wallet files safely: a power cut or a full disk never leaves a half written backup
+// in place of a good one, and the file is only readable by its owner
+// and finding the removable drives to write them to (the internal disk is wiped after use)
+// and overwriting files before deleting them
+//
+use crate::utils::hex;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// Expand a leading ~ to the home directory (the shell doesn't do it for input typed in the program)
+pub fn expandhome(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// Write a file atomically: write a temporary file next to it, flush it to the disk and move it in place
+// an existing file is only replaced when overwrite is true, otherwise the move fails with AlreadyExists
+// (also when the file was created after the user was asked, the check is done by the move itself)
+pub fn writeatomic(path: &Path, contents: &[u8], overwrite: bool) -> io::Result<()> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file name"))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let temp = dir.join(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+
+    let result = writesynced(&temp, contents).and_then(|()| {
+        if overwrite {
+            fs::rename(&temp, path)
+        } else {
+            renamenew(&temp, path, contents)
+        }
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result?;
+
+    // the rename is only on the disk once the directory is flushed
+    syncdir(dir)
+}
+
+// Move a file to a name that must not exist yet, the kernel refuses if it does (RENAME_NOREPLACE),
+// or a hard link does (it never replaces a file), or on file systems without either (FAT on some systems)
+// the file is written again in place, created with O_EXCL
+fn renamenew(temp: &Path, path: &Path, contents: &[u8]) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    match renamenoreplace(temp, path) {
+        // not supported by this kernel or file system
+        Err(e) if matches!(e.raw_os_error(), Some(libc::EINVAL) | Some(libc::ENOSYS)) => {}
+        result => return result,
+    }
+
+    match fs::hard_link(temp, path) {
+        Ok(()) => fs::remove_file(temp),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Err(e),
+        Err(_) => {
+            fs::remove_file(temp)?;
+            let result = writesynced(path, contents);
+            // never leave a half written file, unless it is someone else's
+            if let Err(e) = &result {
+                if e.kind() != io::ErrorKind::AlreadyExists {
+                    let _ = fs::remove_file(path);
+                }
+            }
+            result
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn renamenoreplace(from: &Path, to: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let from = CString::new(from.as_os_str().as_bytes())?;
+    let to = CString::new(to.as_os_str().as_bytes())?;
+    // SAFETY: both paths are valid NUL terminated strings that live until the call returns
+    let result = unsafe {
+        libc::syscall(libc::SYS_renameat2, libc::AT_FDCWD, from.as_ptr(), libc::AT_FDCWD, to.as_ptr(), libc::RENAME_NOREPLACE)
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Create a new file only readable by its owner, write it and flush it to the disk
+fn writesynced(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn syncdir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+// directories can't be opened as files on windows, the rename is flushed by the file system
+#[cfg(not(unix))]
+fn syncdir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+// overwrite passes when wiping a file: zeros, ones, random
+const WIPE_PATTERNS: [Option<u8>; 3] = [Some(0x00), Some(0xff), None];
+
+// Overwrite a file several times (flushing every pass to the disk), then rename it and delete it
+// flash drives and copy-on-write file systems may keep old copies of the data, see the warning shown to the user
+pub fn wipefile(path: &Path) -> io::Result<()> {
+    // only regular files (following a link would wipe its target and leave the link)
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a regular file"));
+    }
+    let size = metadata.len();
+
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    for pattern in WIPE_PATTERNS {
+        file.seek(SeekFrom::Start(0))?;
+        let mut left = size;
+        while left > 0 {
+            let chunk = &mut buffer[..left.min(64 * 1024) as usize];
+            match pattern {
+                Some(byte) => chunk.fill(byte),
+                None => getrandom::getrandom(chunk).map_err(|e| io::Error::other(e.to_string()))?,
+            }
+            file.write_all(chunk)?;
+            left -= chunk.len() as u64;
+        }
+        file.sync_all()?;
+    }
+    file.set_len(0)?;
+    file.sync_all()?;
+    drop(file);
+
+    // a random name hides the file name in the directory before it is removed
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut random = [0u8; 8];
+    getrandom::getrandom(&mut random).map_err(|e| io::Error::other(e.to_string()))?;
+    let renamed = dir.join(hex(&random));
+    fs::rename(path, &renamed)?;
+    fs::remove_file(&renamed)?;
+    syncdir(dir)
+}
+
+// The mount points of the mounted removable drives (USB drives, SD cards) the wallet can be saved to
+pub fn removablemedia() -> Vec<PathBuf> {
+    let devices = removabledevices(Path::new("/sys/block"));
+    match fs::read_to_string("/proc/mounts") {
+        Ok(mounts) => removablemounts(&mounts, &devices),
+        Err(_) => vec![],
+    }
+}
+
+// The block devices that are removable (USB hard drives say they are not, but they are on the USB bus)
+pub fn removabledevices(sysblock: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(sysblock) else {
+        return vec![];
+    };
+    let mut devices: Vec<String> = entries
+        .flatten()
+        .filter(|entry| {
+            let removable = fs::read_to_string(entry.path().join("removable")).is_ok_and(|r| r.trim() == "1");
+            let usb = fs::canonicalize(entry.path()).is_ok_and(|path| path.to_string_lossy().contains("/usb"));
+            removable || usb
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    devices.sort();
+    devices
+}
+
+// The writable mount points of the devices (or their partitions) in /proc/mounts
+pub fn removablemounts(mounts: &str, devices: &[String]) -> Vec<PathBuf> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            let name = fields[0].strip_prefix("/dev/")?;
+            let readonly = fields[3].split(',').any(|option| option == "ro");
+
+            // the device itself (sdb) or one of its partitions (sdb1, mmcblk0p1)
+            let ondevice = devices.iter().any(|device| {
+                name.strip_prefix(device.as_str()).is_some_and(|partition| {
+                    let partition = partition.strip_prefix('p').unwrap_or(partition);
+                    partition.chars().all(|c| c.is_ascii_digit())
+                })
+            });
+
+            // a live system booted from a USB drive has its root on it, that's not a place for backups
+            let mountpoint = unescapemount(fields[1]);
+            (ondevice && !readonly && mountpoint != "/").then(|| PathBuf::from(mountpoint))
+        })
+        .collect()
+}
+
+// /proc/mounts escapes spaces, tabs, new lines and backslashes in mount points as octal (\040)
+fn unescapemount(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes
+            .get(i + 1..i + 4)
+            .filter(|digits| digits.iter().all(|digit| (b'0'..=b'7').contains(digit)))
+            .and_then(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok());
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                unescaped.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
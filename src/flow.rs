@@ -0,0 +1,155 @@
+// Module: flow
+// The interactive scramble and recover flow, it only talks to the user through the terminal
+//
+use crate::constants::*;
+use crate::crypto::*;
+use crate::error::ScramblerError;
+use crate::input::*;
+use crate::terminal::Terminal;
+use crate::utils::*;
+use crate::walletfile::{today, WalletInfo};
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+// Scramble a new wallet or recover an existing one, deriving the key with the given cost
+// and saving wallets to the removable drives media finds (files::removablemedia, or fixed ones in the tests)
+// the password, the key and the words are wiped when dropped, also when an error is returned
+pub fn run(term: &mut dyn Terminal, cost: &KdfCost, media: &dyn Fn() -> Vec<PathBuf>) -> Result<(), ScramblerError> {
+    // Ask the user if they want to scramble a new wallet or recover an existing one
+    let recover = choose(
+        term,
+        "What would you like to do?",
+        &["Scramble a new wallet", "Recover an existing wallet"],
+    )? == 1;
+
+    // init the language, words vector and what the wallet file recorded based on the action
+    let (mut lang, words, info, walletfile) = if recover {
+        // ask if to recover the wallet from a file
+        recoverfromfile(term)?
+    } else {
+        // scramble a new wallet
+        (0, vec![], None, None)
+    };
+    let mut walletsize = words.len();
+    let mut words = Zeroizing::new(words);
+
+    // the key derivation must have the cost the wallet was scrambled with
+    if let Some(info) = &info {
+        if info.profile != cost.name {
+            return Err(ScramblerError::Input(format!(
+                "The wallet was scrambled with the \"{}\" key derivation profile, this program uses \"{}\".",
+                info.profile, cost.name
+            )));
+        }
+    }
+
+    // get the language if not recovering from a file using walletsize=0 to see if it was recovered from file, lang 0 is valid)
+    if walletsize == 0 {
+        // get the language from the user
+        lang = choose(term, "What wordlist would you like to use?", &LANG)?;
+    }
+
+    // get the key derivation scheme from the wallet file or from the user
+    let kdf = if let Some(info) = &info {
+        info.kdf
+    } else if recover {
+        choose(term, "Which key derivation scheme was used to scramble the wallet?", &KDF)?
+    } else {
+        choose(term, "Which key derivation scheme would you like to use?", &KDF)?
+    };
+
+    // check the key derivation fits in memory before going any further
+    preflight(term, kdf, cost)?;
+
+    // a hardware fault while scrambling would silently produce a backup that can never be recovered
+    let verify = !recover
+        && choose(
+            term,
+            "Verify the key derivation by running it twice? (catches faulty RAM or CPU, doubles the time)",
+            &["Yes (recommended on old hardware)", "No"],
+        )? == 0;
+
+    // get the random salt words (this removes them from words recovered from a file)
+    let salt = getsalt(term, recover, lang, &mut words, info.as_ref().map(|info| info.saltwords))?;
+    if !words.is_empty() {
+        walletsize = words.len();
+    }
+
+    // get the password from the user
+    let password = getpassword(term, recover)?;
+
+    // get the optional wallet label from the wallet file or from the user
+    let label = match &info {
+        Some(info) => info.label.clone().into_bytes(),
+        None => getlabel(term, recover)?,
+    };
+
+    // derive the secret key from the password and the label
+    let secretkey = derive_key(term, &password, &label, &salt, kdf, cost)?;
+
+    // derive the key a second time and make sure both keys match
+    if verify {
+        verify_key(term, &password, &label, &salt, kdf, cost, &secretkey)?;
+    }
+
+    // secure wipe the password
+    drop(password);
+
+    // get the wallet size if not recovering from a file
+    if walletsize == 0 {
+
+        walletsize = getwalletsize(term)?;
+    }
+    
+    // get the wallet words if not recovering from a file
+    if words.is_empty() {
+
+        words = getwords(term, walletsize, lang)?;
+    }
+
+    // scramble the wallet words using the secret key
+    let mut newwords = Zeroizing::new(scramblewords(&words, &secretkey, lang)?);
+    
+    // secure wipe the secret key
+    drop(secretkey);
+
+    // the salt words are stored unscrambled after the scrambled words
+    if !recover {
+        newwords.extend_from_slice(&salt);
+    }
+
+    // print the new words to the user
+    term.println("");
+    printwords(term, &newwords, lang, recover);
+    if !recover && !salt.is_empty() {
+        term.println(&format!(
+            "\nThe last {} words are the random salt words, keep them with the scrambled words.",
+            SALT_WORDS
+        ));
+    }
+    let newinfo = WalletInfo {
+        kdf,
+        profile: cost.name.to_string(),
+        label: String::from_utf8_lossy(&label).into_owned(),
+        saltwords: salt.len(),
+        created: today(),
+    };
+    if !recover {
+        // if not recovering from a file, ask the user if they want to save the wallet
+        savewallet(term, &newwords, lang, &newinfo, media)?;
+    } else if info.is_none() && walletfile.is_some() {
+        // a .txt file of an older version only has the words, offer to save them with the wordlist
+        // (the user may have picked it) and everything else that was asked
+        term.println("\nThe wallet file is from an older version, the scrambled words can be saved in a .json file");
+        term.println("that also records the wordlist, the key derivation scheme, the salt words and the label.");
+        let mut scrambled = Zeroizing::new(words.to_vec());
+        scrambled.extend_from_slice(&salt);
+        savewallet(term, &scrambled, lang, &newinfo, media)?;
+    }
+    // the wallet file recovered from, or a draft of the words, can be deleted
+    wipefiles(term, walletfile.as_deref())?;
+
+    term.println("\nPress Enter to exit");
+    term.readline()?;
+    Ok(())
+}
@@ -0,0 +1,343 @@
+// Module: flowtests
+// End-to-end tests of the interactive flow with a scripted terminal and the reduced test cost
+//
+use crate::constants::*;
+use crate::crypto::TEST_COST;
+use crate::flow::run;
+use crate::romanize::readingmatches;
+use crate::terminal::Script;
+use crate::walletfile::*;
+use crate::wordlists::WORDS;
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// the wallet files are read and written in the current directory, which is shared by all the tests
+static CURRENT_DIR: Mutex<()> = Mutex::new(());
+
+// Run the flow with the given input lines, returns the exit code (None if it finished) and the output
+// with the error message shown like main does
+fn play(lines: &[&str]) -> (Option<i32>, String) {
+    playon(&[], lines)
+}
+
+// Run the flow with these removable drives mounted
+fn playon(media: &[PathBuf], lines: &[&str]) -> (Option<i32>, String) {
+    let mut script = Script::new(lines);
+    let code = match run(&mut script, &TEST_COST, &|| media.to_vec()) {
+        Ok(()) => None,
+        Err(e) => {
+            script.output.push_str(&format!("\n{}\n", e));
+            Some(e.code())
+        }
+    };
+    assert_eq!(script.unread(), 0, "the flow didn't read all the input:\n{}", script.output);
+    (code, script.output)
+}
+
+// Run the test in its own empty directory
+fn indir(name: &str, test: impl FnOnce(&PathBuf)) {
+    let _guard = CURRENT_DIR.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!("scrambler-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let previous = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| test(&dir)));
+
+    std::env::set_current_dir(previous).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    if let Err(payload) = result {
+        panic::resume_unwind(payload);
+    }
+}
+
+// The words printed after "Recovered words:" or "New words:"
+fn printed(output: &str, title: &str) -> Vec<String> {
+    let start = output.rfind(title).unwrap_or_else(|| panic!("no {} in:\n{}", title, output));
+    output[start + title.len()..]
+        .lines()
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| line.contains(": ") && !line.is_empty())
+        .map(|line| line.split(": ").nth(1).unwrap().to_string())
+        .collect()
+}
+
+const PHRASE: [&str; 12] = [
+    "peanut", "update", "depth", "march", "stamp", "canal", "horror", "ripple", "alone", "fatal", "parent", "under",
+];
+
+#[test]
+fn scramble_and_recover_from_file() {
+    indir("roundtrip", |dir| {
+        // scramble: English, v2, no verification, salt words, a label, 12 words, saved to wallet.json on the drive
+        let mut lines = vec!["1", "2", "2", "2", "1", "Str0ng-Passw0rd!", "Str0ng-Passw0rd!", "family-vault-2", "12"];
+        lines.extend(PHRASE);
+        lines.extend(["1", "1", "wallet", "1", ""]);
+        let (code, output) = playon(std::slice::from_ref(dir), &lines);
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains(&format!("Removable drive at {}", dir.display())));
+        assert!(output.contains("wallet.json (and read back)"));
+
+        // the file has the scrambled words, the 2 salt words and how they were scrambled
+        let saved = std::fs::read_to_string(dir.join("wallet.json")).unwrap();
+        let (lang, words, info) = decodewallet(&saved).unwrap();
+        let scrambled = printed(&output, "New words:");
+        assert_eq!(scrambled.len(), 14);
+        assert_eq!(words.iter().map(|&word| WORDS[lang][word]).collect::<Vec<_>>(), scrambled);
+        assert_ne!(scrambled[..12], PHRASE);
+        assert_eq!((lang, info.kdf, info.saltwords), (1, 1, 2));
+        assert_eq!((info.profile.as_str(), info.label.as_str()), ("test", "family-vault-2"));
+
+        // recover from the file: only the password is asked, everything else comes from the file
+        // then delete the wallet file and a draft of the words
+        std::fs::write(dir.join("draft.txt"), PHRASE.join(" ")).unwrap();
+        let (code, output) = play(&[
+            "2", "1", "wallet", "Str0ng-Passw0rd!", "Str0ng-Passw0rd!",
+            "2", "2", // delete the wallet file
+            "2", "draft.txt", "2", // delete another file
+            "1", "",
+        ]);
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains("Language: English"));
+        assert!(output.contains("Wallet label: \"family-vault-2\""));
+        assert_eq!(printed(&output, "Recovered words:"), PHRASE);
+        assert_eq!(output.matches("OVERWRITING A FILE IS NOT ALWAYS ENOUGH").count(), 1);
+        assert!(output.contains("wallet.json was overwritten and deleted."));
+        assert!(output.contains("draft.txt was overwritten and deleted."));
+        assert!(!dir.join("wallet.json").exists() && !dir.join("draft.txt").exists());
+
+        // the words in a .txt file of an older version with a typo and a prefix: the typo is fixed,
+        // the word read from the prefix is refused and typed again, then the rest is asked
+        let long = (4..12).find(|&i| scrambled[i].chars().count() > 4).unwrap();
+        let prefix: String = scrambled[long].chars().take(4).collect();
+        let mut lines: Vec<String> = scrambled.iter().map(|word| word.to_string()).collect();
+        lines[3] = "qqqq".to_string();
+        lines[long] = prefix.clone();
+        std::fs::write(dir.join("legacy.txt"), lines.join("\n")).unwrap();
+        let (code, output) = play(&[
+            "2", "1", "legacy", "nope", &scrambled[3], "2", &scrambled[long],
+            "2", "1", "Str0ng-Passw0rd!", "Str0ng-Passw0rd!", "family-vault-2", "2", "1", "",
+        ]);
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains("1 word(s) in the file are not in the English (BIP 39, 2048 words) wordlist"));
+        assert!(output.contains("Word number 4 \"qqqq\" (line 4, column 1) is not in the wordlist."));
+        let number = long + 1;
+        assert!(output.contains(&format!("word {}: \"{}\" is read as \"{}\"", number, prefix, scrambled[long])));
+        assert!(output.contains("1 word(s) in the file were read as other words."));
+        assert!(output.contains(&format!("Word number {} \"{}\" (line {}, column 1) was read as", number, prefix, number)));
+        assert!(output.contains("Invalid word."));
+        assert_eq!(printed(&output, "Recovered words:"), PHRASE);
+
+        // giving up on the word read from the prefix stops
+        let (code, output) = play(&["2", "1", "legacy", &scrambled[3], "2", ""]);
+        assert_eq!(code, Some(EXIT_ABORT), "{}", output);
+    });
+}
+
+// Scramble the test phrase without salt words and save it with the given answers
+fn scramblesave(media: &[PathBuf], save: &[&str]) -> (Option<i32>, String) {
+    let mut lines = vec!["1", "2", "2", "2", "2", "Str0ng-Passw0rd!", "Str0ng-Passw0rd!", "", "12"];
+    lines.extend(PHRASE);
+    lines.push("1");
+    lines.extend(save);
+    lines.extend(["1", ""]);
+    playon(media, &lines)
+}
+
+#[test]
+fn save_never_silently_overwrites() {
+    indir("overwrite", |dir| {
+        let media = [dir.join("usb")];
+        let usb = &media[0];
+        std::fs::create_dir_all(usb.join("backups")).unwrap();
+        std::fs::write(usb.join("old.json"), "the old backup").unwrap();
+
+        // refuse to overwrite, a directory is not a file name, files must stay on the drive
+        let (code, output) = scramblesave(&media, &["1", "old", "1", "1", "backups", "1", "../new", "1", "new"]);
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains("old.json already exists, overwrite it?"));
+        assert!(output.contains("backups is a directory"));
+        assert!(output.contains("is not on the drive at"));
+        assert!(output.contains("new.json (and read back)"));
+        assert_eq!(std::fs::read_to_string(usb.join("old.json")).unwrap(), "the old backup");
+        assert!(decodewallet(&std::fs::read_to_string(usb.join("new.json")).unwrap()).is_ok());
+        assert!(!dir.join("new.json").exists());
+
+        // only the owner can read the backup, and no temporary file is left
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(usb.join("new.json")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(std::fs::read_dir(usb).unwrap().count(), 3);
+
+        // overwrite when confirmed
+        let (code, output) = scramblesave(&media, &["1", "old.json", "2"]);
+        assert_eq!(code, None, "{}", output);
+        assert!(decodewallet(&std::fs::read_to_string(usb.join("old.json")).unwrap()).is_ok());
+    });
+}
+
+#[test]
+fn save_elsewhere_needs_an_override() {
+    indir("elsewhere", |dir| {
+        // no removable drive: look again, refuse the override, then accept it
+        let (code, output) = scramblesave(&[], &["1", "2", "NO", "2", "YES", "wallet"]);
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains("No removable drive found"));
+        assert!(output.contains("WARNING: THE BACKUP WILL NOT BE ON A REMOVABLE DRIVE"));
+        assert!(output.contains("Wallet saved to wallet.json (and read back)"));
+        assert!(dir.join("wallet.json").exists());
+    });
+}
+
+#[test]
+fn recover_with_another_profile() {
+    indir("profile", |dir| {
+        // a wallet scrambled with the full cost can't be recovered with the test cost
+        let info = WalletInfo {
+            kdf: 1,
+            profile: "full".to_string(),
+            label: String::new(),
+            saltwords: 0,
+            created: "2026-10-19".to_string(),
+        };
+        std::fs::write(dir.join("full.json"), encodewallet(1, &[0; 12], &info)).unwrap();
+        let (code, output) = play(&["2", "1", "full"]);
+        assert_eq!(code, Some(EXIT_INPUT));
+        assert!(output.contains("scrambled with the \"full\" key derivation profile"));
+    });
+}
+
+#[test]
+fn recover_words_in_several_wordlists() {
+    indir("ambiguous", |dir| {
+        // the words are in the SLIP39, English and French lists: pick French, then save the file with it
+        let shared = "animal aspect brave civil client crucial distance dragon exact excuse fatal fatigue";
+        std::fs::write(dir.join("shared.txt"), shared).unwrap();
+        let (code, output) = playon(
+            std::slice::from_ref(dir),
+            &["2", "1", "shared", "3", "2", "2", "Str0ng-Passw0rd!", "Str0ng-Passw0rd!", "", "1", "1", "wallet", "1", ""],
+        );
+        assert_eq!(code, None, "{}", output);
+        assert!(output.contains("All the words in the file are in more than one wordlist."));
+        assert!(output.contains(" 3. French (BIP 39, 2048 words)"));
+        assert!(output.contains("Language: French"));
+
+        let (lang, words, info) = decodewallet(&std::fs::read_to_string(dir.join("wallet.json")).unwrap()).unwrap();
+        assert_eq!((lang, info.kdf, info.saltwords), (3, 1, 0));
+        assert!(words.iter().map(|&word| WORDS[3][word]).eq(shared.split(' ')));
+    });
+}
+
+#[test]
+fn recover_golden_vector_manually() {
+    // the second golden vector: English, v2, label "golden-1", salt words "brand amused"
+    // the words are typed one by one, then pasted: too many, with a typo, then numbered (some as prefixes)
+    // the first prefix is read as a word the user doesn't want, and typed again
+    let (code, output) = play(&[
+        "2", "2", "2", "2", "1", "brand", "amused", "Golden-1-Passw0rd!", "Golden-1-Passw0rd!", "golden-1", "12",
+        "coac", "2", "coac", "1", "toilet",
+        "toilet piece robust grab plunge place crawl install bullet quarter romance",
+        "piece robust grab plunge place crawl install bullet quater romance",
+        "3. piece, 4. robust, 5. grab, 6. plun, 7. place, 8. crawl, 9. install, 10. bullet, 11. quarter, 12. romance",
+        "1", "1", "",
+    ]);
+    assert_eq!(code, None, "{}", output);
+    assert!(output.contains("11 words entered, only 10 words are left to enter."));
+    assert!(output.contains("word 9: \"quater\" (line 1, column 53)"));
+    assert_eq!(output.matches("word 1: \"coac\" is read as \"coach\"").count(), 2);
+    assert!(output.contains("word 6: \"plun\" is read as \"plunge\""));
+    assert_eq!(printed(&output, "Recovered words:"), PHRASE);
+}
+
+#[test]
+fn chinese_words_typed_in_pinyin() {
+    // "shi4" is 16 words: the last choice is none of them, then the first one is picked
+    // a reading of only one word is picked too (the first time it is not the right one)
+    // one letter is the start of too many readings, more letters are asked for
+    let mut lines = vec!["1", "10", "2", "2", "2", "weak", "weak", "YES", "", "12", "s", "shi4", "17", "shi4", "1", "neng2", "2", "neng2", "1", "shuo", "1"];
+    lines.extend(WORDS[LANG_CHINESE_SIMPLIFIED][..9].iter().copied());
+    lines.extend(["2", "1", ""]);
+    let (code, output) = play(&lines);
+    assert_eq!(code, None, "{}", output);
+    let starting = readingmatches("s", LANG_CHINESE_SIMPLIFIED).len();
+    assert!(starting > MAX_READING_CHOICES);
+    assert!(output.contains(&format!("{} words have a reading starting with \"s\", please type more letters.", starting)));
+    assert!(output.contains("\"shi4\" can be several words, which one is it?"));
+    assert!(output.contains("1. 是 (shi4)"));
+    assert!(output.contains("\"neng2\" is read as this word, is it the right one?"));
+    assert!(output.contains("\"shuo\" is read as this word, is it the right one?"));
+    assert!(output.contains("1. 说 (shuo1)"));
+    assert!(!output.contains("Are these the right words?"));
+    assert_eq!(printed(&output, "New words:").len(), 12);
+}
+
+#[test]
+fn invalid_input_is_asked_again() {
+    let mut lines = vec![
+        "abc", // not a number
+        "3",   // not a choice
+        "1", "2", "2", "2", "2",
+        "weak", "weaker", // passwords don't match
+        "weak", "weak", "NO", // weak password, not confirmed
+        "weak", "weak", "YES",
+        "", // no label
+        "40", "12",
+        "abandn", // typo
+    ];
+    lines.extend(PHRASE);
+    lines.extend(["2", "1", ""]);
+    let (code, output) = play(&lines);
+    assert_eq!(code, None, "{}", output);
+    assert!(output.contains("Invalid input. Please enter a valid input."));
+    assert!(output.contains("Invalid choice. Please try again."));
+    assert!(output.contains("Passwords do not match"));
+    assert!(output.contains("Weak Passord."));
+    assert!(output.contains("Invalid wallet size."));
+    assert!(output.contains("Invalid word.") && output.contains(" -> abandon"));
+    assert_eq!(printed(&output, "New words:").len(), 12);
+}
+
+#[test]
+fn recover_from_missing_file() {
+    indir("missing", |_| {
+        let (code, output) = play(&["2", "1", "nowallet"]);
+        assert_eq!(code, Some(EXIT_IO));
+        assert!(output.contains("Failed to read nowallet.txt"));
+    });
+}
+
+#[test]
+fn recover_from_bad_files() {
+    indir("bad", |dir| {
+        // too few words
+        std::fs::write(dir.join("short.txt"), "abandon\nability\n").unwrap();
+        let (code, output) = play(&["2", "1", "short"]);
+        assert_eq!(code, Some(EXIT_INPUT));
+        assert!(output.contains("Wallet size must be between 12 and 35 words, the file has 2."));
+
+        // words from no wordlist
+        std::fs::write(dir.join("unknown.txt"), "notaword\n".repeat(12)).unwrap();
+        let (code, output) = play(&["2", "1", "unknown"]);
+        assert_eq!(code, Some(EXIT_INPUT));
+        assert!(output.contains("The wallet file contains words not found in any supported language."));
+
+        // a typo that isn't fixed
+        std::fs::write(dir.join("typo.txt"), format!("abandonn {}", "abandon ".repeat(11))).unwrap();
+        let (code, output) = play(&["2", "1", "typo", ""]);
+        assert_eq!(code, Some(EXIT_ABORT));
+        assert!(output.contains(" -> abandon") && output.contains("Word number 1 \"abandonn\" (line 1, column 1)"));
+        assert_eq!(output.matches("\"abandonn\"").count(), 1);
+
+        // too many words for a wallet without salt words
+        std::fs::write(dir.join("long.txt"), "abandon\n".repeat(34)).unwrap();
+        let (code, output) = play(&["2", "1", "long", "2", "2", "2"]);
+        assert_eq!(code, Some(EXIT_INPUT));
+        assert!(output.contains("The wallet file has too many words, it should end with salt words."));
+    });
+}
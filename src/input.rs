@@ -2,18 +2,26 @@
 // Module for getting input from the user
 //
 use crate::constants::*;
+use crate::crypto::{estimatetime, kdfmemory, randomsalt, KdfCost};
+use crate::error::ScramblerError;
+use crate::files::*;
+use crate::parse::*;
+use crate::romanize::*;
+use crate::terminal::Terminal;
 use crate::utils::*;
-use std::io::{self, Write};
-use std::process;
+use crate::walletfile::*;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use zeroize::Zeroizing;
 
 // Function to choose an action from a list of choices
-pub fn choose(action: &str, choices: &[&str]) -> usize {
+pub fn choose(term: &mut dyn Terminal, action: &str, choices: &[&str]) -> Result<usize, ScramblerError> {
     // Display the action and choices to the user
 
     assert!(!choices.is_empty(), "Choices cannot be empty");
 
     // show the paction to the user
-    println!("\n{}\n", action);
+    term.println(&format!("\n{}\n", action));
 
     // customize the prompt based on the number of choices ( 1 or 2 vs 1 to n)
     let prompt = if choices.len() > 2 {
@@ -28,60 +36,66 @@ pub fn choose(action: &str, choices: &[&str]) -> usize {
             
             // add a space before single digit choices for better alignment
             let space = if index < 9 { " " } else { "" };
-            println!("{}{}. {}", space, index + 1, choice);
+            term.println(&format!("{}{}. {}", space, index + 1, choice));
         }
 
         // get the user input allowing only numbers
-        let input = getinput(&prompt, NUMBERS);
+        let input = getinput(term, &prompt, NUMBERS)?;
 
         // parse the input as a number and check if it is a valid choice
-        match input.trim().parse::<usize>() {
-            Ok(num) if num >= 1 && num <= choices.len() => {
-                
-                // return the choice as a 0-based index
-                return num - 1;
-            }
+        match parsechoice(&input, choices.len()) {
+            // return the choice as a 0-based index
+            Ok(choice) => return Ok(choice),
 
             // show an error message if the choice is invalid
-            _ => println!("\nInvalid choice. Please try again.\n"),
+            Err(e) => term.println(&format!("\n{}\n", e)),
         }
     }
 }
 
 // Function to get input from the user and validate it
-pub fn getinput(prompt: &str, allowed: &str) -> String {
+pub fn getinput(term: &mut dyn Terminal, prompt: &str, allowed: &str) -> Result<String, ScramblerError> {
     
     // Get input from the user and validate it
-    let mut input = String::new();
     loop {
         
-        // Show the prompt and get the input
-        print!("\n{}", prompt);
-        io::stdout().flush().unwrap();
-
-        // Clear the input buffer
-        input.clear();
-        io::stdin().read_line(&mut input).unwrap();
+        // Show the prompt and get the input (wiped when dropped, it might be a password)
+        term.print(&format!("\n{}", prompt));
+        let input = Zeroizing::new(term.readline()?);
         let input = input.trim();
 
         // Check if the input is valid
         if input.chars().all(|c| allowed.contains(c)) {
-            return input.to_string();
+            return Ok(input.to_string());
         } else {
-            println!("\nInvalid input. Please enter a valid input.");
+            term.println("\nInvalid input. Please enter a valid input.");
         }
     }
 }
 
+// Get a file path from the user (any characters, mount points of removable media have all kinds of names)
+pub fn getpath(term: &mut dyn Terminal, prompt: &str) -> Result<PathBuf, ScramblerError> {
+    loop {
+        term.print(&format!("\n{}", prompt));
+        let input = term.readline()?;
+        let input = input.trim();
+        if input.is_empty() {
+            term.println("Please enter a file path.");
+            continue;
+        }
+        return Ok(expandhome(input));
+    }
+}
+
 // Get a password from the user and validate it
-pub fn getpassword(recover: bool) -> Vec<u8> {
+pub fn getpassword(term: &mut dyn Terminal, recover: bool) -> Result<Zeroizing<Vec<u8>>, ScramblerError> {
     
     // allowed characters for the password: upper case, lower case, numbers, special characters
     let allowed = format!("{}{}{}{}", UPPER, LOWER, NUMBERS, SPECIAL);
     if !recover {
         
         // if the user is not recovering a wallet, show a warning about the password strength
-        println!(
+        term.println(
         "\nIt's extremely important to choose a strong password\n\
         Nothing would help you if your password is cracked or guessed.\n\
         12 chars long and a mix of upper, lower, numbers & special chars is recommended.\n"
@@ -91,20 +105,20 @@ pub fn getpassword(recover: bool) -> Vec<u8> {
     // loop until the user enters a valid password
     loop {
         
-        // get the password from the user two times
-        let password = getinput("Enter password: ", &allowed);
-        let password2 = getinput("Enter password again: ", &allowed);
+        // get the password from the user two times (both are wiped when dropped)
+        let password = Zeroizing::new(getinput(term, "Enter password: ", &allowed)?);
+        let password2 = Zeroizing::new(getinput(term, "Enter password again: ", &allowed)?);
 
         // check if the passwords match
         if password != password2 {
-            println!("\nPasswords do not match");
+            term.println("\nPasswords do not match");
             continue;
         }
 
         if recover {
             
             // don't check the password strength if the user is recovering a wallet
-            return password.into_bytes();
+            return Ok(Zeroizing::new(password.as_bytes().to_vec()));
         }
 
         // check if the password has at least one upper case, one lower case, one number, one special character
@@ -117,195 +131,590 @@ pub fn getpassword(recover: bool) -> Vec<u8> {
             // show a warning if the password is weak
 
             // reuqire the user to confirm if they want to continue with a weak password
-            let agree=getinput("\nWeak Passord. Sure you want to continue? (type \"YES\" in capitals to continue): ", UPPER);
+            let agree=getinput(term, "\nWeak Passord. Sure you want to continue? (type \"YES\" in capitals to continue): ", UPPER)?;
             if agree == "YES" {
                 // User confirmed to continue with a weak password
-                println!("\nRemember your password, it CANNOT be recovered.\n");
-                return password.into_bytes();
+                term.println("\nRemember your password, it CANNOT be recovered.\n");
+                return Ok(Zeroizing::new(password.as_bytes().to_vec()));
             }
         } else {
             // password is strong
-            println!("\nRemember your password, it CANNOT be recovered.\n");
-            return password.into_bytes();
+            term.println("\nRemember your password, it CANNOT be recovered.\n");
+            return Ok(Zeroizing::new(password.as_bytes().to_vec()));
         }
     }
 }
 
+// Check that the key derivation fits in the available memory before asking for the password
+// (every scheme needs the same Argon2 memory, so there is no other scheme to offer when it doesn't fit)
+pub fn preflight(term: &mut dyn Terminal, kdf: usize, cost: &KdfCost) -> Result<(), ScramblerError> {
+    let needed = kdfmemory(kdf, cost);
+    match availablememory() {
+        None => term.println(&format!("\nCould not check the available memory, the key derivation needs {}.", gib(needed))),
+        Some(available) if available >= needed + MEMORY_MARGIN => {
+            term.println(&format!("\nThe key derivation needs {} of memory ({} available).", gib(needed), gib(available)));
+        }
+        Some(available) => return Err(ScramblerError::Memory { needed: needed + MEMORY_MARGIN, available }),
+    }
+
+    // estimate the time it will take
+    let estimate = estimatetime(kdf, cost)?.as_secs();
+    term.println(&format!("Estimated key derivation time: about {} minutes and {} seconds.", estimate / 60, estimate % 60));
+    Ok(())
+}
+
+// Get an optional wallet label from the user, it is mixed into the key derivation
+// so one password can protect several wallets without reusing the same key
+pub fn getlabel(term: &mut dyn Terminal, recover: bool) -> Result<Vec<u8>, ScramblerError> {
+    if recover {
+        term.println("\nIf the wallet was scrambled with a label, enter the exact same label.");
+    } else {
+        term.println(
+        "\nA wallet label (e.g. \"family-vault-2\") makes the key unique to this wallet.\n\
+        Use a different label for every wallet you protect with the same password.\n\
+        The label is not secret, but it is REQUIRED for recovery - store it with the backup."
+        );
+    }
+
+    // allowed characters for the label: upper case, lower case, numbers and a few separators
+    let allowed = format!("{}{}{}{}", UPPER, LOWER, NUMBERS, LABEL_SPECIAL);
+    let label = getinput(term, "Enter a wallet label (leave empty for none): ", &allowed)?;
+
+    if !label.is_empty() && !recover {
+        term.println(&format!("\nWallet label: \"{}\" (case sensitive)", label));
+    }
+    Ok(label.into_bytes())
+}
+
+// Ask the user about random salt words, generate them for a new wallet
+// or get them from the recovered words (or from the user) when recovering
+// (saltwords is the number of salt words recorded in the wallet file, if it was read from one)
+pub fn getsalt(
+    term: &mut dyn Terminal,
+    recover: bool,
+    lang: usize,
+    words: &mut Vec<usize>,
+    saltwords: Option<usize>,
+) -> Result<Vec<usize>, ScramblerError> {
+    if !recover {
+        let salt = choose(
+            term,
+            "Would you like to add random salt words? (protects against precomputed password tables)",
+            &["Yes (recommended)", "No"],
+        )?;
+        if salt == 1 {
+            return Ok(vec![]);
+        }
+
+        // generate the salt words, they will be added unscrambled after the scrambled words
+        let salt = randomsalt(lang)?;
+        term.println(&format!(
+            "\n{} random salt words will be added after your scrambled words,\n\
+            they are not secret but they are REQUIRED for recovery.",
+            SALT_WORDS
+        ));
+        return Ok(salt);
+    }
+
+    // the wallet file says if there are salt words (its size was checked when it was read)
+    if let Some(saltwords) = saltwords {
+        return Ok(words.split_off(words.len() - saltwords));
+    }
+
+    let salt = choose(
+        term,
+        "Does your backup end with random salt words?",
+        &["Yes", "No"],
+    )?;
+    if salt == 1 {
+        // without salt words the recovered file must be a valid wallet on its own
+        if words.len() > MAX_WORDS {
+            return Err(ScramblerError::Input(
+                "The wallet file has too many words, it should end with salt words.".to_string(),
+            ));
+        }
+        return Ok(vec![]);
+    }
+
+    if words.is_empty() {
+        // the words are entered manually, get the salt words first
+        term.println(&format!("\nEnter the {} salt words (the last words of your backup).", SALT_WORDS));
+        return Ok(getwords(term, SALT_WORDS, lang)?.to_vec());
+    }
+
+    // the salt words are the last words of the recovered file
+    if words.len() < MIN_WORDS + SALT_WORDS {
+        return Err(ScramblerError::Input("The wallet file is too short to contain salt words.".to_string()));
+    }
+    Ok(words.split_off(words.len() - SALT_WORDS))
+}
+
 // promot the user to get the number of words in the wallet
-pub fn getwalletsize() -> usize {
+pub fn getwalletsize(term: &mut dyn Terminal) -> Result<usize, ScramblerError> {
     loop {
         // get the input from the user allowiung only numbers
         let input = getinput(
-            "\nEnter the number of words in your wallet (12-33): ",
+            term,
+            &format!("\nEnter the number of words in your wallet ({}-{}): ", MIN_WORDS, MAX_WORDS),
             NUMBERS,
-        );
-        match input.trim().parse::<usize>() {
-            Ok(w) if (12..=33).contains(&w) => return w,
-            _ => println!("\nInvalid wallet size. Enter a number between 12 and 33."),
+        )?;
+        match parsewalletsize(&input) {
+            Ok(size) => return Ok(size),
+            Err(e) => term.println(&format!("\n{}", e)),
         }
     }
 }
 
 // get the words from the user and validate them
-pub fn getwords(walletsize: usize, lang: usize) -> Vec<usize> {
+// they are the real wallet words when scrambling, so they are wiped when dropped
+pub fn getwords(term: &mut dyn Terminal, walletsize: usize, lang: usize) -> Result<Zeroizing<Vec<usize>>, ScramblerError> {
     // Ensure wallet size does not exceed the maximum allowed
     if walletsize > MAX_WORDS {
-        panic!("Wallet size cannot exceed {}", MAX_WORDS);
+        return Err(ScramblerError::Input(format!("Wallet size cannot exceed {}", MAX_WORDS)));
     }
     // Create a vector to store the indexes of the words
-    let mut indexes: Vec<usize> = vec![0; walletsize];
-    println!("\nPlease enter the words one by one.\nIf you don't know the full word, type the starting letters,\nThe program will suggest possible words.\n");
-    io::stdout().flush().expect("Failed to flush stdout");
+    let mut indexes: Zeroizing<Vec<usize>> = Zeroizing::new(vec![0; walletsize]);
+    term.println("\nPlease enter the words one by one, or paste them all at once (numbered or not).\nThe first letters are enough when only one word starts with them (4 letters always are in English and SLIP39).\nIf you don't know the full word, type the starting letters,\nThe program will suggest possible words.\n");
+    if romanized(0, lang).is_some() {
+        term.println("The words can also be typed in latin letters: romaji, the revised romanization of Korean or pinyin\n(with tone numbers or marks, or without tones), you then pick the word from the words with that reading.\n");
+    }
 
-    for (i, index) in indexes.iter_mut().enumerate().take(walletsize) {
-        loop {
-            // Prompt the user to enter the word
-            print!("Enter word number {}: ", i + 1);
-            io::stdout().flush().expect("Failed to flush stdout");
-            let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .expect("Failed to read input");
-            let word = input.trim();
-
-            // Check if the word exists in the word list
-            if let Some(word_index) = WORDS[lang].iter().position(|&w| w == word) {
-                *index = word_index;
-                break;
-            } else {
-                // Find suggestions based on the entered word
-                let suggestions = find_suggestions(word, &WORDS[lang]);
-
-                // Show an error message and suggestions
-                println!("\nInvalid word. Please enter a valid word from the word list.");
-                if !suggestions.is_empty() {
-                    println!("\nDid you mean one of these?");
-                    for suggestion in suggestions {
-                        println!(" -> {}", suggestion);
+    let mut i = 0;
+    while i < walletsize {
+        // Prompt the user to enter the word
+        term.print(&format!("Enter word number {}: ", i + 1));
+        let input = Zeroizing::new(term.readline()?);
+        let tokens = tokenize(&input);
+
+        // several words were pasted, they are the next words of the wallet
+        if tokens.len() > 1 {
+            if i + tokens.len() > walletsize {
+                term.println(&format!(
+                    "\n{} words entered, only {} words are left to enter.\n",
+                    tokens.len(),
+                    walletsize - i
+                ));
+                continue;
+            }
+            match parsewords(&input, lang) {
+                Ok(words) => {
+                    let words = Zeroizing::new(words);
+                    if !confirmreadas(term, lang, &readas(&input, lang, &words, i + 1))? {
+                        term.println("\nPlease enter them again.\n");
+                        continue;
                     }
+                    indexes[i..i + words.len()].copy_from_slice(&words);
+                    i += words.len();
                 }
+                Err(e) => term.println(&format!("\n{}\nPlease enter them again.\n", e)),
+            }
+            continue;
+        }
+
+        // Check if the word exists in the word list
+        let word = tokens.first().map_or("", |token| token.word.as_str());
+        if let Some(word_index) = pickword(term, word, lang)? {
+            // a romanized word was just picked, a word read from a prefix is confirmed
+            let picked = !readingmatches(word, lang).is_empty();
+            if picked || confirmreadas(term, lang, &readas(&input, lang, &[word_index], i + 1))? {
+                indexes[i] = word_index;
+                i += 1;
             }
         }
     }
     // Return the indexes of the words as a vector
-    indexes
+    Ok(indexes)
+}
+
+// The words entered as a prefix (or without accents) that were read as a word of the list:
+// their position (first is the position of the first word), what was entered and the word
+fn readas(text: &str, lang: usize, words: &[usize], first: usize) -> Vec<(usize, Token, usize)> {
+    tokenize(text)
+        .into_iter()
+        .zip(words)
+        .enumerate()
+        .filter(|(_, (token, &word))| token.word != WORDS[lang][word])
+        .map(|(position, (token, &word))| (first + position, token, word))
+        .collect()
+}
+
+// Show the words that were read as other words and ask the user if they are right, true if there was nothing to ask
+fn confirmreadas(term: &mut dyn Terminal, lang: usize, read: &[(usize, Token, usize)]) -> Result<bool, ScramblerError> {
+    if read.is_empty() {
+        return Ok(true);
+    }
+    term.println("");
+    for (position, token, word) in read {
+        term.println(&format!("  word {}: \"{}\" is read as \"{}\"", position, token.word, WORDS[lang][*word]));
+    }
+    Ok(choose(term, "Are these the right words?", &["Yes", "No"])? == 0)
+}
+
+// Find a typed word, a romanized word is picked by the user from the words it can be, even when there is only one
+// (nothing when the word is not found, the user is told why)
+fn pickword(term: &mut dyn Terminal, word: &str, lang: usize) -> Result<Option<usize>, ScramblerError> {
+    let matches = readingmatches(word, lang);
+    let found = if matches.is_empty() {
+        resolveword(word, lang)
+    } else if matches.len() > MAX_READING_CHOICES && readingwords(word, lang).is_empty() {
+        // the start of a reading can be hundreds of words ("g" in Korean)
+        term.println(&format!("\n{} words have a reading starting with \"{}\", please type more letters.", matches.len(), word));
+        return Ok(None);
+    } else {
+        let mut choices: Vec<String> = matches.iter().map(|&index| withreading(index, lang)).collect();
+        choices.push("None of these".to_string());
+        let choices: Vec<&str> = choices.iter().map(String::as_str).collect();
+        let question = if matches.len() == 1 {
+            format!("\"{}\" is read as this word, is it the right one?", word)
+        } else {
+            format!("\"{}\" can be several words, which one is it?", word)
+        };
+        matches.get(choose(term, &question, &choices)?).copied()
+    };
+    if found.is_none() {
+        term.println("\nInvalid word. Please enter a valid word from the word list.");
+        suggest(term, word, lang);
+    }
+    Ok(found)
+}
+
+// A word with its romanization when it has one: "さくら (sakura)"
+fn withreading(index: usize, lang: usize) -> String {
+    match romanized(index, lang) {
+        Some(reading) => format!("{} ({})", WORDS[lang][index], reading),
+        None => WORDS[lang][index].to_string(),
+    }
+}
+
+// Show the words of the wordlist close to a word that is not in it
+// (for a romanized word, the words with the closest romanization)
+fn suggest(term: &mut dyn Terminal, word: &str, lang: usize) {
+    let mut suggestions: Vec<String> = closestreadings(word, lang).into_iter().map(|index| withreading(index, lang)).collect();
+    if suggestions.is_empty() {
+        suggestions = find_suggestions(word, lang);
+    }
+    if !suggestions.is_empty() {
+        term.println("\nDid you mean one of these?");
+        for suggestion in suggestions {
+            term.println(&format!(" -> {}", suggestion));
+        }
+    }
+}
+
+// Ask the user which wordlist the words of a file are from when they are in more than one
+// (BIP39 English and French share 100 words, SLIP39 and BIP39 English 553,
+// unscrambling with the wrong list gives completely different words)
+fn picklanguage(term: &mut dyn Terminal, mut found: Vec<(usize, Vec<usize>)>) -> Result<(usize, Vec<usize>), ScramblerError> {
+    if found.len() > 1 {
+        term.println("\nAll the words in the file are in more than one wordlist.");
+        let names: Vec<&str> = found.iter().map(|(lang, _)| LANG[*lang]).collect();
+        let choice = choose(term, "Which wordlist was used to scramble the wallet?", &names)?;
+        return Ok(found.swap_remove(choice));
+    }
+    Ok(found.swap_remove(0))
+}
+
+// Let the user enter again some words of a wallet file, one at a time with suggestions: the words that are not
+// in the wordlist (a typo in the file shouldn't stop the recovery) or that were read as another word and rejected
+// (words are the words of the file, None when not in the wordlist), returns all the words
+// or None if the user gave up with an empty answer
+fn repairwords(
+    term: &mut dyn Terminal,
+    mut words: Vec<Option<usize>>,
+    lang: usize,
+    unknown: &[(usize, Token)],
+) -> Result<Option<Vec<usize>>, ScramblerError> {
+    term.println("Check them against your written copy and enter the right words (the file is not changed).");
+
+    for (position, token) in unknown {
+        let problem = match words[position - 1] {
+            Some(index) => format!("was read as \"{}\"", WORDS[lang][index]),
+            None => "is not in the wordlist".to_string(),
+        };
+        term.println(&format!(
+            "\nWord number {} \"{}\" (line {}, column {}) {}.",
+            position, token.word, token.line, token.column, problem
+        ));
+        suggest(term, &token.word, lang);
+        loop {
+            term.print(&format!("Enter word number {} (or nothing to stop): ", position));
+            let input = Zeroizing::new(term.readline()?);
+            let tokens = tokenize(&input);
+            let word = match tokens.as_slice() {
+                [] => return Ok(None),
+                [typed] => typed.word.as_str(),
+                _ => "",
+            };
+            if let Some(index) = pickword(term, word, lang)? {
+                // a romanized word was just picked, a word read from a prefix is confirmed
+                let picked = !readingmatches(word, lang).is_empty();
+                if !picked && !confirmreadas(term, lang, &readas(&input, lang, &[index], *position))? {
+                    continue;
+                }
+                words[position - 1] = Some(index);
+                break;
+            }
+        }
+    }
+    Ok(words.into_iter().collect())
 }
 
 // Save the wallet words to a file
-pub fn savewallet(words: &[usize], lang: usize) {
+// with how they were scrambled, so recovering only needs the password
+pub fn savewallet(
+    term: &mut dyn Terminal,
+    words: &[usize],
+    lang: usize,
+    info: &WalletInfo,
+    media: &dyn Fn() -> Vec<PathBuf>,
+) -> Result<(), ScramblerError> {
     // Ask the user if they want to save the wallet
     let save = choose(
+        term,
         "Would you like to save your scrambled wallet words?",
         &["Yes", "No"],
-    );
+    )?;
     if save == 1 {
         // User does not want to save the wallet
-        return;
+        return Ok(());
     }
 
-    // Get the filename for the wallet
-    println!("file will be saved as .txt in the current directory");
-    let allowed = format!("{}{}{}", UPPER, LOWER, NUMBERS);
-    let filename = getinput(
-        "Enter a filename for your wallet (no extension): ",
-        &allowed,
-    );
-    let filename = format!("{}.txt", filename);
+    // Get the path of the wallet file and write it atomically, never silently replacing an existing file
+    // (a file created after the question is not replaced either, the write itself refuses to)
+    term.println("\nThe wallet is saved as a .json file (the extension is added if the name has none).");
+    let contents = encodewallet(lang, words, info);
+    let path = loop {
+        let mut path = choosepath(term, media)?;
+        if path.is_dir() {
+            term.println(&format!("{} is a directory, enter a file name.", path.display()));
+            continue;
+        }
+        if path.extension().is_none() {
+            path.set_extension("json");
+        }
+        let mut overwrite = false;
+        if path.exists() {
+            overwrite = choose(
+                term,
+                &format!("{} already exists, overwrite it? (the backup in it will be lost)", path.display()),
+                &["No, choose another file", "Yes, overwrite it"],
+            )? == 1;
+            if !overwrite {
+                continue;
+            }
+        }
+        match writeatomic(&path, contents.as_bytes(), overwrite) {
+            Ok(()) => break path,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                term.println(&format!("\n{} was created in the meantime, it was not replaced.", path.display()));
+            }
+            Err(e) => return Err(ScramblerError::Io(format!("save the wallet to {}", path.display()), e)),
+        }
+    };
 
-    // try to create the file and write the words to it
-    let mut file = std::fs::File::create(filename.clone()).expect("Failed to create file");
-    for &word in words {
-        // write the word to the file
-        writeln!(file, "{}", WORDS[lang][word]).expect("Failed to write to file");
+    // read the file back to make sure the backup is good
+    let saved = std::fs::read_to_string(&path)
+        .map_err(|e| ScramblerError::Io(format!("read back {}", path.display()), e))?;
+    if decodewallet(&saved) != Ok((lang, words.to_vec(), info.clone())) {
+        return Err(ScramblerError::Io(
+            format!("check {}", path.display()),
+            io::Error::new(io::ErrorKind::InvalidData, "the file read back doesn't match the wallet"),
+        ));
     }
 
-    println!("\nWallet saved to {}", filename);
+    term.println(&format!("\nWallet saved to {} (and read back)", path.display()));
+    Ok(())
 }
 
-// Recover the wallet words from a file
-pub fn recoverfromfile() -> (usize, usize, Vec<usize>) {
+// Ask where to save the wallet: on a removable drive media finds (looked up again on request), or anywhere else after a warning
+fn choosepath(term: &mut dyn Terminal, media: &dyn Fn() -> Vec<PathBuf>) -> Result<PathBuf, ScramblerError> {
+    loop {
+        let media = media();
+        if media.is_empty() {
+            term.println("\nNo removable drive found, plug in a USB drive or an SD card (and mount it).");
+        }
+        let mut choices: Vec<String> = media.iter().map(|mount| format!("Removable drive at {}", mount.display())).collect();
+        choices.push("Look for removable drives again".to_string());
+        choices.push("Somewhere else (NOT recommended)".to_string());
+        let choices: Vec<&str> = choices.iter().map(String::as_str).collect();
+        let choice = choose(term, "Where would you like to save the wallet?", &choices)?;
+
+        if choice < media.len() {
+            // the file must stay on the drive (an absolute path would replace the mount point)
+            let mount = &media[choice];
+            let path = mount.join(getpath(term, "Enter a file name on the drive (e.g. wallet or backups/wallet): ")?);
+            if !path.starts_with(mount) || path.components().any(|c| c == Component::ParentDir) {
+                term.println(&format!("\n{} is not on the drive at {}.", path.display(), mount.display()));
+                continue;
+            }
+            return Ok(path);
+        }
+        if choice == media.len() {
+            continue;
+        }
+
+        // anywhere else needs an explicit override
+        term.println("\n************************************************************************");
+        term.println("*         WARNING: THE BACKUP WILL NOT BE ON A REMOVABLE DRIVE         *");
+        term.println("*                                                                      *");
+        term.println("* This machine's disk should be wiped after use: a backup saved on it  *");
+        term.println("* is lost with the wipe, or left behind for anyone to find if it's not *");
+        term.println("* wiped.  Save it on a removable drive unless you know what you do.    *");
+        term.println("************************************************************************");
+        let agree = getinput(term, "Save it somewhere else anyway? (type \"YES\" in capitals to continue): ", UPPER)?;
+        if agree == "YES" {
+            return getpath(term, "Enter the path of the wallet file (e.g. wallet or /mnt/backup/wallet): ");
+        }
+    }
+}
+
+// a recovered wallet file: the language, the words, how they were scrambled
+// (only recorded in .json files, .txt files of older versions only have the words) and the path of the file
+pub type Recovered = (usize, Vec<usize>, Option<WalletInfo>, Option<PathBuf>);
+
+// Recover the wallet words from a file (the words are empty if not recovering from a file)
+pub fn recoverfromfile(term: &mut dyn Terminal) -> Result<Recovered, ScramblerError> {
     // Ask the user if they want to recover from a file
-    let choice = choose("Do you want to recover from a file?", &["Yes", "No"]);
+    let choice = choose(term, "Do you want to recover from a file?", &["Yes", "No"])?;
     if choice == 1 {
         // User does not want to recover from a file
-        return (0, 0, vec![]);
+        return Ok((0, vec![], None, None));
+    }
+    term.println("\nThe file should be a .json (or an older .txt) wallet file.");
+    let mut path = getpath(term, "Enter the path of your wallet file: ")?;
+
+    // older versions asked for the name without the extension
+    if path.extension().is_none() && !path.exists() {
+        path.set_extension("json");
+        if !path.exists() {
+            path.set_extension("txt");
+        }
     }
-    // assenble the allowed characters for the filename
-    let allowed = format!("{}{}{}", UPPER, LOWER, NUMBERS);
-    println!("\nFile should be a .txt file in the current directory.");
-    let filename = getinput(
-        "Enter the filename of your wallet (no extension): ",
-        &allowed,
-    );
-    let filename = format!("{}.txt", filename);
 
     // try to read the file and recover the wallet words
-    let file = std::fs::read_to_string(&filename).expect("Failed to read file");
-    let readwords: Vec<&str> = file.lines().collect();
-    let walletsize = readwords.len();
-
-    // check if the wallet size is valid
-    if !(12..=33).contains(&walletsize) {
-        panic!("Wallet size must be between 12 and 33 words.");
-    }
-
-    // try to recover the wallet words for each language
-    for (lang_index, words_in_language) in WORDS.iter().enumerate() {
-        // create a vector to store the indexes of the words
-        let mut indices = Vec::with_capacity(walletsize);
-
-        // check if all the words in the file are in the current language
-        let mut all_words_match = true;
-
-        for &word in &readwords {
-            // check if the word is in the word list
-            if let Some(index) = words_in_language.iter().position(|&w| w == word) {
-                // store the index of the word
-                indices.push(index);
-            } else {
-                // the word is not in the word list
-                all_words_match = false;
-                break;
+    let file = std::fs::read_to_string(&path).map_err(|e| ScramblerError::Io(format!("read {}", path.display()), e))?;
+    let (lang, words, info) = if iswalletjson(&file) {
+        let (lang, words, info) = decodewallet(&file)?;
+        (lang, words, Some(info))
+    } else {
+        let (lang, mut words, repaired) = match parsewalletfile(&file) {
+            Ok(found) => {
+                let (lang, words) = picklanguage(term, found)?;
+                (lang, words, vec![])
+            }
+            Err(ParseError::UnknownWords(lang, unknown)) => {
+                term.println(&format!(
+                    "\n{} word(s) in the file are not in the {} wordlist (the language most of the words are in).",
+                    unknown.len(),
+                    LANG[lang]
+                ));
+                let found = tokenize(&file).iter().map(|token| resolveword(&token.word, lang)).collect();
+                let words = repairwords(term, found, lang, &unknown)?.ok_or(ScramblerError::Aborted)?;
+                let repaired: Vec<usize> = unknown.iter().map(|(position, _)| *position).collect();
+                (lang, words, repaired)
             }
+            Err(e) => return Err(e.into()),
+        };
+
+        // the words of the file read as other words (the ones entered again were already confirmed)
+        let read: Vec<(usize, Token, usize)> =
+            readas(&file, lang, &words, 1).into_iter().filter(|(position, ..)| !repaired.contains(position)).collect();
+        if !confirmreadas(term, lang, &read)? {
+            let rejected: Vec<(usize, Token)> = read.into_iter().map(|(position, token, _)| (position, token)).collect();
+            term.println(&format!("\n{} word(s) in the file were read as other words.", rejected.len()));
+            words = repairwords(term, words.into_iter().map(Some).collect(), lang, &rejected)?.ok_or(ScramblerError::Aborted)?;
         }
+        (lang, words, None)
+    };
 
-        // check if all the words in the file are in the current language
-        if all_words_match {
-            // all words are in the current language
-            println!("\nWallet recovered from file: {}\n", filename);
-            println!("Language: {}\n", LANG[lang_index]);
+    term.println(&format!("\nWallet recovered from file: {}\n", path.display()));
+    term.println(&format!("Language: {}\n", LANG[lang]));
+    if let Some(info) = &info {
+        term.println(&format!("Key derivation scheme: {}", KDF[info.kdf]));
+        term.println(&format!("Salt words: {}", info.saltwords));
+        term.println(&format!("Wallet label: \"{}\"", info.label));
+        term.println(&format!("Created: {}\n", info.created));
+    }
+
+    // print the recovered words
+    term.println("here are the words found in the file (before unscambling)");
+    printwords(term, &words, lang, true);
+    term.println("\nTo unscramble the words, enter the password");
+
+    // return the language index, the word indices, how they were scrambled and where they were read from
+    Ok((lang, words, info, Some(path)))
+}
+
+// Offer to securely delete files when done: the wallet file recovered from, or any other file
+// (e.g. a draft of the real words typed before scrambling them)
+pub fn wipefiles(term: &mut dyn Terminal, walletfile: Option<&Path>) -> Result<(), ScramblerError> {
+    let mut walletfile = walletfile.map(Path::to_path_buf);
+    let mut warned = false;
+    loop {
+        let mut choices = vec!["No".to_string()];
+        if let Some(path) = &walletfile {
+            choices.push(format!("Yes, the wallet file {} (only if you have another copy!)", path.display()));
+        }
+        choices.push("Yes, another file (e.g. a draft of your wallet words)".to_string());
+        let choices: Vec<&str> = choices.iter().map(String::as_str).collect();
+        let choice = choose(term, "Would you like to securely delete a file?", &choices)?;
+        if choice == 0 {
+            return Ok(());
+        }
 
-            // print the recovered words
-            println!("here are the words found in the file (before unscambling)");
-            printwords(&indices, lang_index, true);
-            println!("\nTo unscramble the words, enter the password");
+        if !warned {
+            term.println("\n************************************************************************");
+            term.println("*              OVERWRITING A FILE IS NOT ALWAYS ENOUGH                 *");
+            term.println("*                                                                      *");
+            term.println("* The file is overwritten 3 times and then deleted, but USB drives,    *");
+            term.println("* SD cards and SSDs write elsewhere than asked (wear levelling), and   *");
+            term.println("* copy-on-write or journaling file systems (btrfs, ZFS, APFS) may keep *");
+            term.println("* old copies.  Copies made by editors, backups or sync tools are not   *");
+            term.println("* touched.  To be sure, wipe or destroy the whole drive.               *");
+            term.println("************************************************************************");
+            warned = true;
+        }
 
-            // return the wallet size, language index, and word indices
-            return (walletsize, lang_index, indices);
+        let path = match (&walletfile, choice) {
+            (Some(path), 1) => path.clone(),
+            _ => getpath(term, "Enter the path of the file to delete: ")?,
+        };
+        let confirm = choose(
+            term,
+            &format!("Delete {} for good? This can't be undone.", path.display()),
+            &["No", "Yes, delete it"],
+        )?;
+        if confirm == 0 {
+            continue;
+        }
+
+        // a file that can't be wiped is reported, the other files can still be deleted
+        match wipefile(&path) {
+            Ok(()) => {
+                term.println(&format!("\n{} was overwritten and deleted.", path.display()));
+                if walletfile.as_ref() == Some(&path) {
+                    walletfile = None;
+                }
+            }
+            Err(e) => term.println(&format!("\nFailed to delete {}: {}", path.display(), e)),
         }
     }
-    // the words in the file are not in any of the supported languages
-    println!("The wallet file contains words not found in any supported language.");
-    process::exit(1);
 }
 
 // Warn the user if they are connected to the internet and ask if they want to continue
-pub fn warnuser() {
-    println!("\n************************************************************************");
-    println!("*                WARNING: YOU ARE CONNECTED TO THE INTERNET            *");
-    println!("*                        THIS A REALLY BAD IDEA                        *");
-    println!("*                                                                      *");
-    println!("* If there is by chance a maleware on your computer your wallet might  *");
-    println!("* be exposed and lost.  Unless you are just testing this utility,      *");
-    println!("* Please disconnect, and wipe the computer after usage                 *");
-    println!("************************************************************************\n");
-
-    print!("Sure you want to continue? (type \"YES\" in capital letters to continue):");
-    std::io::stdout().flush().unwrap();
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    if input.trim() != "YES" {
-        println!("Exiting...");
-        process::exit(0);
+pub fn warnuser(term: &mut dyn Terminal) -> Result<(), ScramblerError> {
+    term.println("\n************************************************************************");
+    term.println("*                WARNING: YOU ARE CONNECTED TO THE INTERNET            *");
+    term.println("*                        THIS A REALLY BAD IDEA                        *");
+    term.println("*                                                                      *");
+    term.println("* If there is by chance a maleware on your computer your wallet might  *");
+    term.println("* be exposed and lost.  Unless you are just testing this utility,      *");
+    term.println("* Please disconnect, and wipe the computer after usage                 *");
+    term.println("************************************************************************\n");
+
+    term.print("Sure you want to continue? (type \"YES\" in capital letters to continue):");
+    if term.readline()?.trim() != "YES" {
+        return Err(ScramblerError::Aborted);
     }
+    Ok(())
 }
@@ -0,0 +1,418 @@
+// Module: lanes
+// Argon2id (version 0x13) with every lane filled on its own thread
+// the argon2 crate fills the lanes one after the other on a single thread,
+// this produces byte identical output, it only splits the work between threads
+//
+use argon2::{Block, Error, Params};
+use blake2::digest::{Digest, Update, VariableOutput};
+use blake2::{Blake2b512, Blake2bVar};
+use std::thread;
+use zeroize::Zeroize;
+
+// Argon2 constants (RFC 9106)
+const SYNC_POINTS: usize = 4;
+const ADDRESSES_IN_BLOCK: usize = 128;
+const VERSION: u32 = 0x13;
+const ARGON2ID: u32 = 2;
+const MIN_SALT_LEN: usize = 8;
+
+// Raw pointer to the memory blocks shared by the lane threads
+// the memory is split in lanes (rows) and slices (4 columns), a segment is one lane of one slice.
+// All the threads fill the same slice at a time, thread L only writes the segment of lane L
+// and only reads its own segment or blocks of the slices finished before (Segment::fill),
+// the threads of a slice are joined before the next slice starts
+#[derive(Clone, Copy)]
+struct Memory(*mut Block);
+
+// SAFETY: the pointer is only used inside the thread::scope of one slice, while the blocks are borrowed
+// by hash_password_with, and no two threads of a slice ever write the same block or read a block
+// another thread is writing (a thread only writes the segment of its own lane, see above)
+unsafe impl Send for Memory {}
+unsafe impl Sync for Memory {}
+
+impl Memory {
+    // SAFETY (for the callers): the index must be in bounds and the block must not be written by another thread
+    unsafe fn get(&self, index: usize) -> &Block {
+        &*self.0.add(index)
+    }
+
+    // SAFETY (for the callers): the index must be in bounds and the block must not be accessed by another thread
+    unsafe fn set(&self, index: usize, block: Block) {
+        *self.0.add(index) = block;
+    }
+}
+
+// The Argon2 compression function G, one of the implementations below
+pub type Compress = fn(&Block, &Block) -> Block;
+
+// Hash the password into out with Argon2id using the provided memory blocks and one thread per lane
+pub fn hash_password_parallel(
+    params: &Params,
+    password: &[u8],
+    salt: &[u8],
+    out: &mut [u8],
+    blocks: &mut [Block],
+) -> Result<(), Error> {
+    hash_password_with(params, password, salt, out, blocks, compress)
+}
+
+// Hash with the given compression function (the tests check both implementations against the argon2 crate)
+pub fn hash_password_with(
+    params: &Params,
+    password: &[u8],
+    salt: &[u8],
+    out: &mut [u8],
+    blocks: &mut [Block],
+    compress: Compress,
+) -> Result<(), Error> {
+    // validate the inputs the same way the argon2 crate does
+    if salt.len() < MIN_SALT_LEN {
+        return Err(Error::SaltTooShort);
+    }
+    if out.len() < Params::MIN_OUTPUT_LEN {
+        return Err(Error::OutputTooShort);
+    }
+    if out.len() > params.output_len().unwrap_or(Params::MAX_OUTPUT_LEN) {
+        return Err(Error::OutputTooLong);
+    }
+
+    let lanes = params.p_cost() as usize;
+    let block_count = params.block_count();
+    let lane_length = block_count / lanes;
+    let segment_length = lane_length / SYNC_POINTS;
+    let blocks = blocks.get_mut(..block_count).ok_or(Error::MemoryTooLittle)?;
+
+    // H0: hash all the parameters and inputs
+    let mut digest = Blake2b512::new();
+    Digest::update(&mut digest, params.p_cost().to_le_bytes());
+    Digest::update(&mut digest, (out.len() as u32).to_le_bytes());
+    Digest::update(&mut digest, params.m_cost().to_le_bytes());
+    Digest::update(&mut digest, params.t_cost().to_le_bytes());
+    Digest::update(&mut digest, VERSION.to_le_bytes());
+    Digest::update(&mut digest, ARGON2ID.to_le_bytes());
+    Digest::update(&mut digest, (password.len() as u32).to_le_bytes());
+    Digest::update(&mut digest, password);
+    Digest::update(&mut digest, (salt.len() as u32).to_le_bytes());
+    Digest::update(&mut digest, salt);
+    Digest::update(&mut digest, 0u32.to_le_bytes());
+    Digest::update(&mut digest, (params.data().len() as u32).to_le_bytes());
+    Digest::update(&mut digest, params.data());
+    let mut initial_hash = digest.finalize();
+
+    // the first two blocks of each lane are H'(H0 || block || lane)
+    let mut bytes = [0u8; Block::SIZE];
+    for (lane, lane_blocks) in blocks.chunks_exact_mut(lane_length).enumerate() {
+        for (i, block) in lane_blocks[..2].iter_mut().enumerate() {
+            blake2b_long(
+                &[&initial_hash, &(i as u32).to_le_bytes(), &(lane as u32).to_le_bytes()],
+                &mut bytes,
+            );
+            for (word, chunk) in block.as_mut().iter_mut().zip(bytes.chunks_exact(8)) {
+                *word = u64::from_le_bytes(chunk.try_into().expect("8 bytes"));
+            }
+        }
+    }
+    initial_hash.zeroize();
+
+    // fill the memory, the lanes of every slice are filled in parallel
+    // and all of them are finished before the next slice starts
+    let passes = params.t_cost() as usize;
+    let memory = Memory(blocks.as_mut_ptr());
+    for pass in 0..passes {
+        for slice in 0..SYNC_POINTS {
+            thread::scope(|scope| {
+                for lane in 0..lanes {
+                    let segment = Segment {
+                        pass,
+                        slice,
+                        lane,
+                        passes,
+                        lanes,
+                        lane_length,
+                        segment_length,
+                        compress,
+                    };
+                    scope.spawn(move || segment.fill(memory));
+                }
+            });
+        }
+    }
+
+    // the tag is H' of the XOR of the last block of every lane
+    let mut last = blocks[lane_length - 1];
+    for lane in 1..lanes {
+        last ^= &blocks[lane * lane_length + lane_length - 1];
+    }
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip(last.as_ref()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    blake2b_long(&[&bytes], out);
+
+    // wipe the intermediate values
+    last.zeroize();
+    bytes.zeroize();
+
+    Ok(())
+}
+
+// Position of a segment in the memory
+#[derive(Clone, Copy)]
+struct Segment {
+    pass: usize,
+    slice: usize,
+    lane: usize,
+    passes: usize,
+    lanes: usize,
+    lane_length: usize,
+    segment_length: usize,
+    compress: Compress,
+}
+
+impl Segment {
+    // Fill the blocks of the segment, this mirrors the reference implementation
+    fn fill(&self, memory: Memory) {
+        // Argon2id uses data independent addressing in the first half of the first pass
+        let data_independent = self.pass == 0 && self.slice < SYNC_POINTS / 2;
+
+        let zero_block = Block::new();
+        let mut address_block = Block::new();
+        let mut input_block = Block::new();
+        if data_independent {
+            input_block.as_mut()[..6].copy_from_slice(&[
+                self.pass as u64,
+                self.lane as u64,
+                self.slice as u64,
+                (self.lane_length * self.lanes) as u64,
+                self.passes as u64,
+                ARGON2ID as u64,
+            ]);
+        }
+
+        // the first two blocks of each lane are already initialized
+        let first_block = if self.pass == 0 && self.slice == 0 {
+            if data_independent {
+                next_addresses(&mut address_block, &mut input_block, &zero_block, self.compress);
+            }
+            2
+        } else {
+            0
+        };
+
+        let segment_start = self.lane * self.lane_length + self.slice * self.segment_length;
+        for block in first_block..self.segment_length {
+            let cur_index = segment_start + block;
+            let prev_index = if self.slice == 0 && block == 0 {
+                // last block of the lane
+                cur_index + self.lane_length - 1
+            } else {
+                cur_index - 1
+            };
+
+            // SAFETY: the previous block is in bounds (block_count blocks were checked) and in this lane:
+            // earlier in this segment, written by this thread, or for the first block of a pass the last block
+            // of the lane, in the last slice, which no thread writes while the first slice is filled
+            let prev = unsafe { memory.get(prev_index) };
+
+            // get the pseudo random value used to pick the reference block
+            let rand = if data_independent {
+                let address_index = block % ADDRESSES_IN_BLOCK;
+                if address_index == 0 {
+                    next_addresses(&mut address_block, &mut input_block, &zero_block, self.compress);
+                }
+                address_block.as_ref()[address_index]
+            } else {
+                prev.as_ref()[0]
+            };
+
+            // the first slice of the first pass can only reference its own lane
+            let ref_lane = if self.pass == 0 && self.slice == 0 {
+                self.lane
+            } else {
+                (rand >> 32) as usize % self.lanes
+            };
+
+            // number of blocks that can be referenced
+            let same_lane = ref_lane == self.lane;
+            let reference_area_size = if self.pass == 0 {
+                if self.slice == 0 {
+                    block - 1
+                } else if same_lane {
+                    self.slice * self.segment_length + block - 1
+                } else {
+                    self.slice * self.segment_length - usize::from(block == 0)
+                }
+            } else if same_lane {
+                self.lane_length - self.segment_length + block - 1
+            } else {
+                self.lane_length - self.segment_length - usize::from(block == 0)
+            };
+
+            // map the random value to a position in the reference area
+            let mut map = rand & 0xFFFF_FFFF;
+            map = (map * map) >> 32;
+            let relative_position =
+                reference_area_size - 1 - ((reference_area_size as u64 * map) >> 32) as usize;
+            let start_position = if self.pass != 0 && self.slice != SYNC_POINTS - 1 {
+                (self.slice + 1) * self.segment_length
+            } else {
+                0
+            };
+            let ref_index = ref_lane * self.lane_length + (start_position + relative_position) % self.lane_length;
+
+            // SAFETY: ref_index is in bounds (ref_lane < lanes, the position is taken modulo lane_length).
+            // The reference area of another lane never includes the segment of the current slice
+            // (reference_area_size leaves it out and start_position skips it), so the block is in a slice
+            // that was joined before this one started; in this lane it can also be an earlier block of this
+            // segment, written by this thread. Either way no other thread is writing it
+            let reference = unsafe { memory.get(ref_index) };
+            let mut result = (self.compress)(prev, reference);
+
+            // version 0x13 XORs the new block into the old one after the first pass
+            if self.pass != 0 {
+                // SAFETY: cur_index is in this lane's segment of the current slice, which only this thread
+                // reads or writes until the slice is joined
+                result ^= unsafe { memory.get(cur_index) };
+            }
+
+            // SAFETY: cur_index is in this lane's segment of the current slice, no other thread reads
+            // or writes it until the slice is joined, and prev and reference are no longer used
+            unsafe { memory.set(cur_index, result) };
+        }
+
+        address_block.zeroize();
+        input_block.zeroize();
+    }
+}
+
+// Generate the next block of data independent addresses
+fn next_addresses(address_block: &mut Block, input_block: &mut Block, zero_block: &Block, compress: Compress) {
+    input_block.as_mut()[6] += 1;
+    *address_block = compress(zero_block, input_block);
+    *address_block = compress(zero_block, address_block);
+}
+
+// The Argon2 compression function G, compiled with AVX2 when the CPU supports it
+fn compress(x: &Block, y: &Block) -> Block {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the CPU supports AVX2 (checked just above)
+            return unsafe { compress_avx2(x, y) };
+        }
+    }
+
+    compress_generic(x, y)
+}
+
+// The same code as compress_generic, compiled with AVX2
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn compress_avx2(x: &Block, y: &Block) -> Block {
+    compress_generic(x, y)
+}
+
+// The compression functions this CPU can run by name, the last one is the one hash_password_parallel uses
+// (the tests check every one of them)
+pub fn compressors() -> Vec<(&'static str, Compress)> {
+    let mut compressors: Vec<(&'static str, Compress)> = vec![("scalar", compress_generic)];
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2 (checked just above)
+        compressors.push(("avx2", |x, y| unsafe { compress_avx2(x, y) }));
+    }
+    compressors
+}
+
+// The Argon2 compression function G (the rows then the columns of the block through the BLAKE2b round)
+#[inline(always)]
+fn compress_generic(x: &Block, y: &Block) -> Block {
+    let r = *x ^ y;
+    let mut q = r;
+    let v = q.as_mut();
+
+    // apply the permutation on the rows
+    for row in 0..8 {
+        let b = row * 16;
+        permute(v, [b, b + 1, b + 2, b + 3, b + 4, b + 5, b + 6, b + 7, b + 8, b + 9, b + 10, b + 11, b + 12, b + 13, b + 14, b + 15]);
+    }
+
+    // apply the permutation on the columns
+    for column in 0..8 {
+        let b = column * 2;
+        permute(v, [b, b + 1, b + 16, b + 17, b + 32, b + 33, b + 48, b + 49, b + 64, b + 65, b + 80, b + 81, b + 96, b + 97, b + 112, b + 113]);
+    }
+
+    q ^= &r;
+    q
+}
+
+// The BLAKE2b round function applied on 16 words of the block
+#[inline(always)]
+fn permute(v: &mut [u64], i: [usize; 16]) {
+    mix(v, i[0], i[4], i[8], i[12]);
+    mix(v, i[1], i[5], i[9], i[13]);
+    mix(v, i[2], i[6], i[10], i[14]);
+    mix(v, i[3], i[7], i[11], i[15]);
+    mix(v, i[0], i[5], i[10], i[15]);
+    mix(v, i[1], i[6], i[11], i[12]);
+    mix(v, i[2], i[7], i[8], i[13]);
+    mix(v, i[3], i[4], i[9], i[14]);
+}
+
+// The BLAKE2b mixing function with the Argon2 multiplication (BlaMka)
+#[inline(always)]
+fn mix(v: &mut [u64], a: usize, b: usize, c: usize, d: usize) {
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[inline(always)]
+fn blamka(x: u64, y: u64) -> u64 {
+    let low = (x & 0xFFFF_FFFF).wrapping_mul(y & 0xFFFF_FFFF);
+    x.wrapping_add(y).wrapping_add(low.wrapping_mul(2))
+}
+
+// The Argon2 variable length hash function H'
+fn blake2b_long(inputs: &[&[u8]], out: &mut [u8]) {
+    let length = (out.len() as u32).to_le_bytes();
+
+    // short outputs are a single BLAKE2b hash
+    if out.len() <= 64 {
+        let mut digest = Blake2bVar::new(out.len()).expect("valid output length");
+        Update::update(&mut digest, &length);
+        for input in inputs {
+            Update::update(&mut digest, input);
+        }
+        digest.finalize_variable(out).expect("valid output length");
+        return;
+    }
+
+    // longer outputs are a chain of hashes, taking the first half of each one
+    let mut digest = Blake2b512::new();
+    Digest::update(&mut digest, length);
+    for input in inputs {
+        Digest::update(&mut digest, input);
+    }
+    let mut last = digest.finalize();
+    out[..32].copy_from_slice(&last[..32]);
+
+    let mut position = 32;
+    while out.len() - position > 64 {
+        last = Blake2b512::digest(last);
+        out[position..position + 32].copy_from_slice(&last[..32]);
+        position += 32;
+    }
+
+    // the last hash fills the rest of the output
+    let mut digest = Blake2bVar::new(out.len() - position).expect("valid output length");
+    Update::update(&mut digest, &last);
+    digest.finalize_variable(&mut out[position..]).expect("valid output length");
+    last.zeroize();
+}
+
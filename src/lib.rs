@@ -0,0 +1,26 @@
+// Catsec wallet word scrambler, the scrambler binary is in main.rs
+// the library lets the fuzz targets (in fuzz/) reach the parsers
+//
+pub mod bench;
+pub mod constants;
+pub mod crypto;
+pub mod error;
+pub mod files;
+pub mod flow;
+pub mod input;
+pub mod lanes;
+pub mod memory;
+pub mod parse;
+pub mod romanize;
+pub mod selftest;
+pub mod terminal;
+pub mod utils;
+pub mod walletfile;
+pub mod wordlists;
+
+#[cfg(test)]
+mod flowtests;
+#[cfg(test)]
+mod proptests;
+#[cfg(test)]
+mod tests;
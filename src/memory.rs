@@ -0,0 +1,71 @@
+// Module: memory
+// Argon2 working memory that is allocated, locked and wiped by us
+// so password derived blocks are never left behind in freed pages
+//
+use argon2::Block;
+use zeroize::Zeroize;
+
+pub struct ArgonMemory {
+    blocks: Vec<Block>,
+    locked: bool,
+}
+
+impl ArgonMemory {
+    // Allocate the blocks and try to lock them in RAM so they are never swapped to disk
+    pub fn new(count: usize) -> Self {
+        let blocks = vec![Block::new(); count];
+        let locked = lock(&blocks);
+        ArgonMemory { blocks, locked }
+    }
+
+    // Check if the memory is locked in RAM
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    // Get the blocks to hand them to Argon2
+    pub fn blocks(&mut self) -> &mut [Block] {
+        &mut self.blocks
+    }
+
+    // Overwrite all the blocks with zeros
+    pub fn wipe(&mut self) {
+        for block in self.blocks.iter_mut() {
+            block.zeroize();
+        }
+    }
+}
+
+impl Drop for ArgonMemory {
+    // wipe the memory before it is freed
+    fn drop(&mut self) {
+        self.wipe();
+        if self.locked {
+            unlock(&self.blocks);
+        }
+    }
+}
+
+// Lock the memory in RAM, this fails if the user is not allowed to lock that much memory
+#[cfg(unix)]
+fn lock(blocks: &[Block]) -> bool {
+    // SAFETY: the pointer and length describe memory owned by the blocks vector
+    unsafe { libc::mlock(blocks.as_ptr() as *const libc::c_void, std::mem::size_of_val(blocks)) == 0 }
+}
+
+#[cfg(unix)]
+fn unlock(blocks: &[Block]) {
+    // SAFETY: the pointer and length describe memory owned by the blocks vector
+    unsafe {
+        libc::munlock(blocks.as_ptr() as *const libc::c_void, std::mem::size_of_val(blocks));
+    }
+}
+
+// memory locking is not supported on this platform
+#[cfg(not(unix))]
+fn lock(_blocks: &[Block]) -> bool {
+    false
+}
+
+#[cfg(not(unix))]
+fn unlock(_blocks: &[Block]) {}
@@ -0,0 +1,263 @@
+// Module: parse
+// Parsers for everything typed by the user or read from a file: menu choices, wallet sizes, words and wallet files
+// they never panic, bad input is reported with a ParseError (the fuzz targets in fuzz/ check it)
+//
+use crate::constants::*;
+use crate::romanize::readingwords;
+use std::fmt;
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, Zeroizing};
+
+// A word of a mnemonic (lower cased and NFKD normalized, like the wordlists) and where it was found, the line and the column start at 1
+// and the column counts characters, the word is wiped when dropped (it may be a real wallet word)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub word: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Drop for Token {
+    fn drop(&mut self) {
+        self.word.zeroize();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    // not one of the menu choices
+    Choice,
+    // not a wallet size between MIN_WORDS and MAX_WORDS
+    WalletSize,
+    // the word at this position (starting at 1) is not in the wordlist
+    UnknownWord(usize),
+    // these words (with their position, starting at 1) are not in this wordlist
+    UnknownWords(usize, Vec<(usize, Token)>),
+    // the wallet file has this number of words, not between MIN_WORDS and MAX_WORDS + SALT_WORDS
+    FileSize(usize),
+    // the wallet file has words that are not in any wordlist
+    UnknownLanguage,
+    // the JSON wallet file can't be read (the decoder's message)
+    Json(String),
+    // the JSON file is not a wallet file of this program
+    Format,
+    // the wallet file is from a newer version of the program
+    Version(u32),
+    // this field of the wallet file has an invalid value
+    Field(&'static str),
+    // the checksum of the wallet file doesn't match its contents
+    Checksum,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Choice => write!(f, "Invalid choice. Please try again."),
+            ParseError::WalletSize => write!(
+                f,
+                "Invalid wallet size. Enter a number between {} and {}.",
+                MIN_WORDS, MAX_WORDS
+            ),
+            ParseError::UnknownWord(position) => write!(f, "Word number {} is not in the wordlist.", position),
+            ParseError::UnknownWords(lang, unknown) => {
+                write!(f, "These words are not in the {} wordlist:", LANG[*lang])?;
+                for (position, token) in unknown {
+                    write!(
+                        f,
+                        "\n  word {}: \"{}\" (line {}, column {})",
+                        position, token.word, token.line, token.column
+                    )?;
+                }
+                Ok(())
+            }
+            ParseError::FileSize(count) => write!(
+                f,
+                "Wallet size must be between {} and {} words, the file has {}.",
+                MIN_WORDS,
+                MAX_WORDS + SALT_WORDS,
+                count
+            ),
+            ParseError::UnknownLanguage => {
+                write!(f, "The wallet file contains words not found in any supported language.")
+            }
+            ParseError::Json(message) => write!(f, "The wallet file can't be read: {}", message),
+            ParseError::Format => write!(f, "The file is not a wallet file of this program."),
+            ParseError::Version(version) => write!(
+                f,
+                "The wallet file has version {}, this program only reads up to version {}. Use a newer version.",
+                version, WALLET_VERSION
+            ),
+            ParseError::Field(field) => write!(f, "The wallet file has an invalid {}.", field),
+            ParseError::Checksum => write!(f, "The wallet file checksum doesn't match, the file is damaged."),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Parse a menu choice between 1 and the number of choices, returns it as a 0-based index
+pub fn parsechoice(input: &str, choices: usize) -> Result<usize, ParseError> {
+    match input.trim().parse::<usize>() {
+        Ok(choice) if choice >= 1 && choice <= choices => Ok(choice - 1),
+        _ => Err(ParseError::Choice),
+    }
+}
+
+// Parse the number of words in a wallet
+pub fn parsewalletsize(input: &str) -> Result<usize, ParseError> {
+    match input.trim().parse::<usize>() {
+        Ok(size) if (MIN_WORDS..=MAX_WORDS).contains(&size) => Ok(size),
+        _ => Err(ParseError::WalletSize),
+    }
+}
+
+// Find a word in a wordlist (the empty entries padding the SLIP39 list are not words)
+pub fn findword(word: &str, lang: usize) -> Option<usize> {
+    if word.is_empty() {
+        return None;
+    }
+    WORDS[lang].iter().position(|&w| w == word)
+}
+
+// A word without its accents ("éléphant" -> "elephant"), the NFKD form without the combining diacritical marks
+// (the Japanese voicing marks are not in that block, they make different words)
+pub fn foldword(word: &str) -> String {
+    word.nfkd().filter(|c| !('\u{300}'..='\u{36f}').contains(c)).collect()
+}
+
+// The wordlists without accents, folded once
+pub fn foldedwords(lang: usize) -> &'static [String] {
+    static FOLDED: OnceLock<Vec<Vec<String>>> = OnceLock::new();
+    &FOLDED.get_or_init(|| WORDS.iter().map(|list| list.iter().map(|word| foldword(word)).collect()).collect())[lang]
+}
+
+// Find a word (NFKD normalized), or else the only word that is the same without accents,
+// or the only word starting with it, with or without accents (steel plates often only have the first 4 letters,
+// which are enough for every SLIP39 and BIP39 English word), or the only Japanese, Korean or Chinese word
+// with that complete romanization (see romanize.rs, the start of a romanization is only read when typed)
+pub fn resolveword(word: &str, lang: usize) -> Option<usize> {
+    if word.is_empty() {
+        return None;
+    }
+    if let Some(index) = findword(word, lang) {
+        return Some(index);
+    }
+    let folded = Zeroizing::new(foldword(word));
+    if folded.is_empty() {
+        return None;
+    }
+    let folds = foldedwords(lang);
+    unique(folds.iter().map(|w| *w == *folded))
+        .or_else(|| unique(WORDS[lang].iter().map(|w| w.starts_with(word))))
+        .or_else(|| unique(folds.iter().map(|w| w.starts_with(folded.as_str()))))
+        .or_else(|| match readingwords(word, lang)[..] {
+            [index] => Some(index),
+            _ => None,
+        })
+}
+
+// The index of the only match
+fn unique(matches: impl Iterator<Item = bool>) -> Option<usize> {
+    let mut found = matches.enumerate().filter(|&(_, matched)| matched).map(|(index, _)| index);
+    match (found.next(), found.next()) {
+        (Some(index), None) => Some(index),
+        _ => None,
+    }
+}
+
+// Split a mnemonic into words, the same way for files and typed or pasted words:
+// separated by spaces (also the Japanese ideographic space), commas or new lines (LF or CRLF),
+// numbered or not ("1. abandon", "01 abandon", "1) abandon"), in any case, with or without a UTF-8 BOM
+// in any Unicode normal form (the words are NFKD normalized like the BIP39 wordlists)
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut tokens = vec![];
+    for (line, content) in text.lines().enumerate() {
+        let mut start = 0;
+        let mut word = String::new();
+        // a separator at the end of the line ends the last word
+        for (column, c) in content.chars().chain([' ']).enumerate() {
+            if !c.is_whitespace() && c != ',' {
+                if word.is_empty() {
+                    start = column;
+                }
+                word.push(c);
+                continue;
+            }
+            if word.is_empty() {
+                continue;
+            }
+
+            // drop the numbering (words never have digits)
+            let unnumbered = word.trim_start_matches(|c: char| c.is_ascii_digit());
+            let unnumbered = if unnumbered.len() < word.len() {
+                unnumbered.strip_prefix(['.', ')', ':']).unwrap_or(unnumbered)
+            } else {
+                unnumbered
+            };
+            if !unnumbered.is_empty() {
+                tokens.push(Token {
+                    word: Zeroizing::new(unnumbered.to_lowercase()).nfkd().collect(),
+                    line: line + 1,
+                    column: start + word.chars().count() - unnumbered.chars().count() + 1,
+                });
+            }
+            word.zeroize();
+        }
+    }
+    tokens
+}
+
+// Parse a mnemonic into the indexes of its words in the wordlist (words or unique prefixes), reports all the unknown words
+pub fn parsewords(text: &str, lang: usize) -> Result<Vec<usize>, ParseError> {
+    let mut unknown = vec![];
+    let mut words = vec![];
+    for (i, token) in tokenize(text).into_iter().enumerate() {
+        match resolveword(&token.word, lang) {
+            Some(word) => words.push(word),
+            None => unknown.push((i + 1, token)),
+        }
+    }
+    if unknown.is_empty() {
+        Ok(words)
+    } else {
+        words.zeroize();
+        Err(ParseError::UnknownWords(lang, unknown))
+    }
+}
+
+// Parse a wallet file (the words, optionally followed by the salt words) and find its language
+// returns every language that has all the words (a few words are in more than one list, the user picks one)
+// with the word indexes in that language
+pub fn parsewalletfile(contents: &str) -> Result<Vec<(usize, Vec<usize>)>, ParseError> {
+    let tokens = tokenize(contents);
+    if !(MIN_WORDS..=MAX_WORDS + SALT_WORDS).contains(&tokens.len()) {
+        return Err(ParseError::FileSize(tokens.len()));
+    }
+
+    // the languages that have all the words, or else all the words or prefixes
+    // (a prefix of a word in one list can be a word of another list)
+    let matching = |find: fn(&str, usize) -> Option<usize>| -> Vec<(usize, Vec<usize>)> {
+        (0..WORDS.len())
+            .filter_map(|lang| {
+                let words = tokens.iter().map(|token| find(&token.word, lang)).collect::<Option<Vec<usize>>>()?;
+                Some((lang, words))
+            })
+            .collect()
+    };
+    let mut found = matching(findword);
+    if found.is_empty() {
+        found = matching(resolveword);
+    }
+    if !found.is_empty() {
+        return Ok(found);
+    }
+
+    // otherwise show the unknown words of the wordlist that has the most of them
+    let matches = |lang: usize| tokens.iter().filter(|token| resolveword(&token.word, lang).is_some()).count();
+    match (0..WORDS.len()).max_by_key(|&lang| (matches(lang), std::cmp::Reverse(lang))) {
+        Some(lang) if matches(lang) > 0 => Err(parsewords(contents, lang).unwrap_err()),
+        _ => Err(ParseError::UnknownLanguage),
+    }
+}
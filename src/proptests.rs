@@ -0,0 +1,112 @@
+// Module: proptests
+// Property tests of the scrambling core, with random keys, languages and wallet sizes
+//
+use crate::constants::*;
+use crate::lanes::*;
+use crate::utils::*;
+use crate::wordlists::WORDS;
+
+use argon2::{Algorithm, Argon2, Block, Params, Version};
+use proptest::prelude::*;
+
+// A random key, language and wallet size
+fn wallet() -> impl Strategy<Value = ([u8; 64], usize, usize)> {
+    (prop::array::uniform32(any::<u8>()), prop::array::uniform32(any::<u8>()), 0..WORDS.len(), MIN_WORDS..=MAX_WORDS)
+        .prop_map(|(low, high, lang, size)| {
+            let mut key = [0u8; 64];
+            key[..32].copy_from_slice(&low);
+            key[32..].copy_from_slice(&high);
+            (key, lang, size)
+        })
+}
+
+// A random wallet with random words from its wordlist
+fn wallet_with_words() -> impl Strategy<Value = ([u8; 64], usize, Vec<usize>)> {
+    wallet().prop_flat_map(|(key, lang, size)| {
+        let words = prop::collection::vec(0..1usize << getwordlistbitsize(lang), size);
+        (Just(key), Just(lang), words)
+    })
+}
+
+proptest! {
+    #[test]
+    fn scrambling_twice_returns_the_words((key, lang, words) in wallet_with_words()) {
+        let scrambled = scramblewords(&words, &key, lang).unwrap();
+        prop_assert_eq!(scramblewords(&scrambled, &key, lang).unwrap(), words);
+    }
+
+    #[test]
+    fn scrambled_words_are_never_empty((key, lang, words) in wallet_with_words()) {
+        // the SLIP39 list is padded with "" up to 2048 entries
+        for word in scramblewords(&words, &key, lang).unwrap() {
+            prop_assert!(!WORDS[lang][word].is_empty());
+        }
+    }
+
+    #[test]
+    fn chunks_fit_in_the_wordlist_bits((key, lang, size) in wallet()) {
+        let bits = getwordlistbitsize(lang);
+        for chunk in dividekey(&key, size, bits).unwrap() {
+            prop_assert!((chunk as usize) < 1 << bits);
+        }
+    }
+
+    #[test]
+    fn chunks_are_the_key_bits_in_order((key, lang, size) in wallet()) {
+        // bit b of chunk i is bit (i * bits + b) of the key, counting from the lowest bit of the first byte
+        let bits = getwordlistbitsize(lang);
+        for (i, chunk) in dividekey(&key, size, bits).unwrap().into_iter().enumerate() {
+            for b in 0..bits {
+                let position = i * bits + b;
+                prop_assert_eq!((chunk >> b) & 1, ((key[position / 8] >> (position % 8)) & 1) as u16);
+            }
+        }
+    }
+}
+
+proptest! {
+    // every case scrambles the whole wordlist, fewer cases are enough
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn every_position_is_a_bijection((key, lang, words) in wallet_with_words(), position in 0..MAX_WORDS) {
+        // every word at a given position scrambles to a different word, so each scrambled word has exactly one original
+        let position = position % words.len();
+        let count = 1 << getwordlistbitsize(lang);
+        let mut seen = vec![false; count];
+        let mut words = words;
+        for word in 0..count {
+            words[position] = word;
+            let scrambled = scramblewords(&words, &key, lang).unwrap()[position];
+            prop_assert!(scrambled < count && !seen[scrambled]);
+            seen[scrambled] = true;
+        }
+    }
+}
+
+proptest! {
+    // every case runs Argon2 twice per compression function
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn lanes_hash_like_the_argon2_crate(
+        password in prop::collection::vec(any::<u8>(), 0..64),
+        salt in prop::collection::vec(any::<u8>(), 8..32),
+        lanes in 1..=4u32,
+        memory in 0..300u32,
+        passes in 1..=3u32,
+        length in 4..=80usize,
+    ) {
+        let params = Params::new(8 * lanes + memory, passes, lanes, Some(length)).unwrap();
+        let mut expected = vec![0u8; length];
+        let mut blocks = vec![Block::new(); params.block_count()];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password_into_with_memory(&password, &salt, &mut expected, &mut blocks)
+            .unwrap();
+        for (_, compress) in compressors() {
+            let mut output = vec![0u8; length];
+            hash_password_with(&params, &password, &salt, &mut output, &mut blocks, compress).unwrap();
+            prop_assert_eq!(&output, &expected);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+// Module: romanize
+// Typing the Japanese, Korean and Chinese words in latin letters, for computers without an input method:
+// romaji (Hepburn, Kunrei or as typed in an input method), the revised romanization of Korean
+// and pinyin (with or without tones), the user picks a typed romanized word from the words it can be,
+// a file or pasted words can only have the complete reading of one word
+//
+use crate::constants::*;
+use crate::wordlists::pinyin::{PINYIN_READINGS, TRADITIONAL_READINGS};
+use crate::wordlists::WORDS;
+use levenshtein::levenshtein;
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
+
+// Kana and their romaji, the Hepburn spelling first (it is the one shown), then the other spellings
+const KANA: [(&str, &str); 145] = [
+    ("きゃ", "kya"), ("きゅ", "kyu"), ("きょ", "kyo"),
+    ("しゃ", "sha"), ("しゃ", "sya"), ("しゅ", "shu"), ("しゅ", "syu"), ("しょ", "sho"), ("しょ", "syo"),
+    ("ちゃ", "cha"), ("ちゃ", "tya"), ("ちゃ", "cya"), ("ちゅ", "chu"), ("ちゅ", "tyu"), ("ちゅ", "cyu"),
+    ("ちょ", "cho"), ("ちょ", "tyo"), ("ちょ", "cyo"),
+    ("にゃ", "nya"), ("にゅ", "nyu"), ("にょ", "nyo"),
+    ("ひゃ", "hya"), ("ひゅ", "hyu"), ("ひょ", "hyo"),
+    ("みゃ", "mya"), ("みゅ", "myu"), ("みょ", "myo"),
+    ("りゃ", "rya"), ("りゅ", "ryu"), ("りょ", "ryo"),
+    ("ぎゃ", "gya"), ("ぎゅ", "gyu"), ("ぎょ", "gyo"),
+    ("じゃ", "ja"), ("じゃ", "zya"), ("じゃ", "jya"), ("じゅ", "ju"), ("じゅ", "zyu"), ("じゅ", "jyu"),
+    ("じょ", "jo"), ("じょ", "zyo"), ("じょ", "jyo"),
+    ("ぢゃ", "dya"), ("ぢゅ", "dyu"), ("ぢょ", "dyo"),
+    ("びゃ", "bya"), ("びゅ", "byu"), ("びょ", "byo"),
+    ("ぴゃ", "pya"), ("ぴゅ", "pyu"), ("ぴょ", "pyo"),
+    ("うぃ", "wi"), ("うぇ", "we"),
+    ("あ", "a"), ("い", "i"), ("う", "u"), ("え", "e"), ("お", "o"),
+    ("か", "ka"), ("き", "ki"), ("く", "ku"), ("け", "ke"), ("こ", "ko"),
+    ("さ", "sa"), ("し", "shi"), ("し", "si"), ("す", "su"), ("せ", "se"), ("そ", "so"),
+    ("た", "ta"), ("ち", "chi"), ("ち", "ti"), ("つ", "tsu"), ("つ", "tu"), ("て", "te"), ("と", "to"),
+    ("な", "na"), ("に", "ni"), ("ぬ", "nu"), ("ね", "ne"), ("の", "no"),
+    ("は", "ha"), ("ひ", "hi"), ("ふ", "fu"), ("ふ", "hu"), ("へ", "he"), ("ほ", "ho"),
+    ("ま", "ma"), ("み", "mi"), ("む", "mu"), ("め", "me"), ("も", "mo"),
+    ("や", "ya"), ("ゆ", "yu"), ("よ", "yo"),
+    ("ら", "ra"), ("り", "ri"), ("る", "ru"), ("れ", "re"), ("ろ", "ro"),
+    ("ら", "la"), ("り", "li"), ("る", "lu"), ("れ", "le"), ("ろ", "lo"),
+    ("わ", "wa"), ("を", "wo"),
+    ("が", "ga"), ("ぎ", "gi"), ("ぐ", "gu"), ("げ", "ge"), ("ご", "go"),
+    ("ざ", "za"), ("じ", "ji"), ("じ", "zi"), ("ず", "zu"), ("ぜ", "ze"), ("ぞ", "zo"),
+    ("だ", "da"), ("ぢ", "ji"), ("ぢ", "di"), ("づ", "zu"), ("づ", "du"), ("づ", "dzu"), ("で", "de"), ("ど", "do"),
+    ("ば", "ba"), ("び", "bi"), ("ぶ", "bu"), ("べ", "be"), ("ぼ", "bo"),
+    ("ぱ", "pa"), ("ぴ", "pi"), ("ぷ", "pu"), ("ぺ", "pe"), ("ぽ", "po"),
+    ("ん", "n"), ("ん", "nn"), ("ん", "n'"),
+    ("ぃ", "xi"), ("ゃ", "xya"), ("ゅ", "xyu"), ("ょ", "xyo"), ("っ", "xtu"), ("っ", "xtsu"),
+];
+
+// The revised romanization of the Hangul initial consonants, vowels and final consonants
+// (the finals as pronounced at the end of a syllable, then when they are followed by a vowel)
+const INITIALS: [&str; 19] = ["g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p", "h"];
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we", "wi", "yu", "eu", "ui", "i",
+];
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p", "t", "t", "ng", "t", "t", "k",
+    "t", "p", "t",
+];
+const LINKED: [&str; 28] = [
+    "", "g", "kk", "ks", "n", "nj", "n", "d", "r", "lg", "lm", "lb", "ls", "lt", "lp", "r", "m", "b", "ps", "s", "ss", "ng", "j",
+    "ch", "k", "t", "p", "",
+];
+
+// Check a word is typed in latin letters (tones as numbers or accents, ü, apostrophes and dashes between syllables)
+fn islatin(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_lowercase())
+        && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "'-:".contains(c) || ('\u{300}'..='\u{36f}').contains(&c))
+}
+
+// The indexes of the words a romanized word is the complete reading of (nothing if the wordlist can't be romanized)
+pub fn readingwords(word: &str, lang: usize) -> Vec<usize> {
+    let typed = typedreadings(word, lang);
+    wordswith(lang, |reading| typed.contains(reading))
+}
+
+// The indexes of the words a typed romanized word can be: the words it is the reading of,
+// or else the words whose reading starts with it (the user picks one of them, even when there is only one)
+pub fn readingmatches(word: &str, lang: usize) -> Vec<usize> {
+    let typed = typedreadings(word, lang);
+    let exact = wordswith(lang, |reading| typed.contains(reading));
+    if !exact.is_empty() {
+        return exact;
+    }
+    wordswith(lang, |reading| typed.iter().any(|t| reading.starts_with(t.as_str())))
+}
+
+// The indexes of the words with a reading that matches
+fn wordswith(lang: usize, matches: impl Fn(&String) -> bool) -> Vec<usize> {
+    readings(lang)
+        .iter()
+        .enumerate()
+        .filter(|(_, readings)| readings.iter().any(&matches))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+// A romanized word as the readings are written (the kana it can be for Japanese), nothing if it isn't romanized
+fn typedreadings(word: &str, lang: usize) -> Vec<String> {
+    let word: String = word.nfkd().collect();
+    if !islatin(&word) || word.len() > 64 {
+        return vec![];
+    }
+    match lang {
+        LANG_JAPANESE => kanareadings(&word),
+        LANG_KOREAN => vec![word.replace(['\'', '-'], "")],
+        LANG_CHINESE_SIMPLIFIED | LANG_CHINESE_TRADITIONAL => vec![pinyin(&word)],
+        _ => vec![],
+    }
+}
+
+// The words whose reading is the closest to a romanized word that matches none (up to 3)
+pub fn closestreadings(word: &str, lang: usize) -> Vec<usize> {
+    let word: String = word.nfkd().collect();
+    if !islatin(&word) || !matches!(lang, LANG_JAPANESE | LANG_KOREAN | LANG_CHINESE_SIMPLIFIED | LANG_CHINESE_TRADITIONAL) {
+        return vec![];
+    }
+    // the tones only count when they are typed
+    let typed = if lang == LANG_KOREAN { word.replace(['\'', '-'], "") } else { pinyin(&word) };
+    let toned = typed.chars().any(|c| c.is_ascii_digit());
+    let mut distances: Vec<(usize, usize)> = (0..WORDS[lang].len())
+        .filter_map(|index| {
+            let mut reading = romanized(index, lang)?;
+            reading.retain(|c| c != '\'' && (toned || !c.is_ascii_digit()));
+            Some((levenshtein(&typed, &reading), index))
+        })
+        .filter(|&(distance, _)| distance <= 2)
+        .collect();
+    distances.sort();
+    distances.into_iter().take(3).map(|(_, index)| index).collect()
+}
+
+// The romanization of a word shown next to it (Hepburn romaji, revised romanization, pinyin with tone numbers)
+pub fn romanized(index: usize, lang: usize) -> Option<String> {
+    let word = WORDS[lang].get(index)?;
+    match lang {
+        LANG_JAPANESE => Some(romaji(&word.nfc().collect::<String>())),
+        LANG_KOREAN => Some(hangul(word, true)),
+        LANG_CHINESE_SIMPLIFIED | LANG_CHINESE_TRADITIONAL => pinyinreadings(index, lang).split(' ').next().map(String::from),
+        _ => None,
+    }
+}
+
+// The readings of every word of a list: the kana (NFC) of the Japanese words, the Korean words romanized
+// with and without the sound of the final consonant moving to the next syllable, the pinyin with and without tones
+fn readings(lang: usize) -> &'static [Vec<String>] {
+    static READINGS: OnceLock<Vec<Vec<Vec<String>>>> = OnceLock::new();
+    let all = READINGS.get_or_init(|| {
+        (0..WORDS.len())
+            .map(|lang| {
+                WORDS[lang]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| match lang {
+                        LANG_JAPANESE => vec![word.nfc().collect()],
+                        LANG_KOREAN => vec![hangul(word, true), hangul(word, false)],
+                        LANG_CHINESE_SIMPLIFIED | LANG_CHINESE_TRADITIONAL => pinyinreadings(index, lang)
+                            .split(' ')
+                            .flat_map(|reading| [reading.to_string(), reading.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()])
+                            .collect(),
+                        _ => vec![],
+                    })
+                    .collect()
+            })
+            .collect()
+    });
+    &all[lang]
+}
+
+// The pinyin readings of a Chinese word (the two lists share them, except where the characters are read differently)
+fn pinyinreadings(index: usize, lang: usize) -> &'static str {
+    match TRADITIONAL_READINGS.iter().find(|&&(word, _)| word == index) {
+        Some((_, readings)) if lang == LANG_CHINESE_TRADITIONAL => readings,
+        _ => PINYIN_READINGS[index],
+    }
+}
+
+// The kana a romaji word can be written with (n can be ん or start the next syllable, ji and zu have two kana each)
+// only the kana that start a word of the list are kept
+fn kanareadings(word: &str) -> Vec<String> {
+    let latin: Vec<char> = word.chars().filter(|&c| c != '-').collect();
+    let mut found = vec![];
+    spellkana(&latin, 0, String::new(), readings(LANG_JAPANESE), &mut found);
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn spellkana(latin: &[char], start: usize, kana: String, words: &[Vec<String>], found: &mut Vec<String>) {
+    if !words.iter().any(|word| word[0].starts_with(&kana)) {
+        return;
+    }
+    if start == latin.len() {
+        found.push(kana);
+        return;
+    }
+    let rest: String = latin[start..].iter().collect();
+
+    // a doubled consonant is a small tsu (tch too: matcha)
+    let next = latin.get(start + 1).copied();
+    if (next == Some(latin[start]) && !"aiueon'".contains(latin[start])) || rest.starts_with("tch") {
+        spellkana(latin, start + 1, kana.clone() + "っ", words, found);
+    }
+    // an apostrophe only separates syllables (n'a)
+    if latin[start] == '\'' {
+        spellkana(latin, start + 1, kana.clone(), words, found);
+    }
+    for (syllable, spelling) in KANA {
+        if rest.starts_with(spelling) {
+            spellkana(latin, start + spelling.chars().count(), kana.clone() + syllable, words, found);
+        }
+    }
+}
+
+// The Hepburn romaji of a kana word
+fn romaji(kana: &str) -> String {
+    let chars: Vec<char> = kana.chars().collect();
+    let mut syllables: Vec<&str> = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        // the two kana syllables (kya) first
+        let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+        let one = chars[i].to_string();
+        match KANA.iter().find(|(syllable, _)| *syllable == two && two.chars().count() == 2) {
+            Some((_, spelling)) => {
+                syllables.push(spelling);
+                i += 2;
+            }
+            None => {
+                syllables.push(KANA.iter().find(|(syllable, _)| *syllable == one).map_or("", |(_, spelling)| spelling));
+                i += 1;
+            }
+        }
+    }
+
+    let mut latin = String::new();
+    for (i, syllable) in syllables.iter().enumerate() {
+        let next = syllables.get(i + 1).copied().unwrap_or("");
+        match *syllable {
+            // the small tsu doubles the next consonant
+            "xtu" => latin.push_str(if next.starts_with("ch") { "t" } else { &next[..next.len().min(1)] }),
+            "n" if next.starts_with(['a', 'i', 'u', 'e', 'o', 'y']) => latin.push_str("n'"),
+            _ => latin.push_str(syllable),
+        }
+    }
+    latin
+}
+
+// The revised romanization of a Korean word, linked: a final consonant followed by a vowel is read with it (gugeo)
+fn hangul(word: &str, linked: bool) -> String {
+    let syllables: Vec<u32> = word
+        .nfc()
+        .map(|c| c as u32)
+        .filter(|c| (0xac00..=0xd7a3).contains(c))
+        .map(|c| c - 0xac00)
+        .collect();
+    let mut latin = String::new();
+    for (i, &syllable) in syllables.iter().enumerate() {
+        let (initial, vowel, last) = ((syllable / 588) as usize, (syllable % 588 / 28) as usize, (syllable % 28) as usize);
+        let nextinitial = syllables.get(i + 1).map(|next| (next / 588) as usize);
+        let previous = i.checked_sub(1).map(|p| (syllables[p] % 28) as usize);
+
+        // the initial was already written with the final of the previous syllable (linked), ㄹㄹ is ll
+        if !(linked && initial == 11 && previous.is_some_and(|p| p != 0 && p != 21)) {
+            latin.push_str(if linked && initial == 5 && previous == Some(8) { "l" } else { INITIALS[initial] });
+        }
+        latin.push_str(VOWELS[vowel]);
+        latin.push_str(if linked && nextinitial == Some(11) { LINKED[last] } else { FINALS[last] });
+    }
+    latin
+}
+
+// A pinyin syllable as in the readings: tone marks as tone numbers at the end, ü (or u:) as v
+fn pinyin(word: &str) -> String {
+    let mut latin = String::new();
+    let mut tone = None;
+    for c in word.replace("u:", "v").chars() {
+        match c {
+            '\u{304}' => tone = Some('1'),
+            '\u{301}' => tone = Some('2'),
+            '\u{30c}' => tone = Some('3'),
+            '\u{300}' => tone = Some('4'),
+            '\u{308}' if latin.ends_with('u') => {
+                latin.pop();
+                latin.push('v');
+            }
+            '\'' | '-' => {}
+            c if ('\u{300}'..='\u{36f}').contains(&c) => {}
+            c => latin.push(c),
+        }
+    }
+    latin.extend(tone);
+    latin
+}
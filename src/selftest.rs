@@ -0,0 +1,165 @@
+// Module: selftest
+// Power-on self-tests, run every time before any key is derived
+// a miscompiled, corrupted or tampered build refuses to run instead of producing a bad backup
+//
+use crate::constants::*;
+use crate::crypto::*;
+use crate::error::ScramblerError;
+use crate::lanes::hash_password_parallel;
+use crate::utils::*;
+
+use argon2::{Block, Params};
+use sha2::{Digest, Sha256};
+
+// SHA3-512 test vectors (FIPS 202)
+const SHA3_ABC: &str = "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0";
+const SHA3_EMPTY: &str = "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26";
+
+// Argon2id version 0x13 test vectors: password "password", salt "somesalt", 256 KiB, 2 passes
+// (1 and 2 lanes are from the reference implementation, 4 lanes from the argon2 crate)
+const ARGON2ID: [(u32, &str); 3] = [
+    (1, "9dfeb910e80bad0311fee20f9c0e2b12c17987b4cac90c2ef54d5b3021c68bfe"),
+    (2, "6d093c501fd5999645e0ea3bf620d7b8be7fd2db59c20d9fff9539da2bf57037"),
+    (4, "be29d1c497593959cd701e5ceefe8a6fbda26d9b3892c08cff261e0a94bab2b1"),
+];
+
+// key derivation vectors with the reduced test cost: (scheme, label, salt words, key), scheme 1 is v2
+const KDF_VECTORS: [(usize, &str, &[usize], &str); 3] = [
+    (KDF_V1, "", &[],
+        "3569dc2cb99fc2bb5cc39f0865533dea752ae280a7b81ce15bfc456c89666019640965447d6f095c68201aa246a92be614584f5676973214987813a77e7766e8",
+    ),
+    (1, "self-test", &[1234, 567],
+        "76738a179824fe84146c1348a0c3c694c7b4fc894c4598ccaa610ecfa8b8903e59b59f3eb47377ba205ac4a8250e14d296c3891d9f9c176d6242c60623e4aaa9",
+    ),
+    (KDF_CASCADE, "self-test", &[1234, 567],
+        "6302b77bb53cf7dac4c3fcb288047c1955f5c6a3e3a6bc8b8bd0856b15f0138b46c5fc6ddfdd4fc6676ddadcfe20be3adca208c1030fc75123b1c740555940c7",
+    ),
+];
+const KDF_PASSWORD: &[u8] = b"Self-test-Passw0rd!";
+
+// dividekey vectors, the key bytes are (i * 37 + 11) and the bits are taken LSB first
+const DIVIDE_11: [u16; 33] = [
+    11, 678, 1513, 591, 1692, 1565, 1548, 1002, 1954, 1432, 71, 1435, 5, 1355, 1010, 167, 1593,
+    107, 1698, 358, 383, 632, 408, 1372, 1488, 862, 252, 1202, 744, 423, 1918, 528, 1127,
+];
+const DIVIDE_10: [u16; 33] = [
+    11, 332, 933, 637, 452, 954, 816, 352, 637, 488, 716, 71, 822, 22, 600, 810, 239, 581, 483,
+    525, 424, 179, 383, 240, 609, 737, 266, 983, 794, 271, 150, 698, 211,
+];
+
+// SHA-256 of the published wordlist files (one word per line, ending with a new line), the BIP39 ones are
+// bip-0039/<language>.txt in github.com/bitcoin/bips, the SLIP39 one is of this repository's list:
+// it still has to be compared with slip-0039/wordlist.txt in github.com/satoshilabs/slips
+const WORDLIST_DIGESTS: [&str; 11] = [
+    "bcc4555340332d169718aed8bf31dd9d5248cb7da6e5d355140ef4f1e601eec3",
+    "2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda",
+    "7e80e161c3e93d9554c2efb78d4e3cebf8fc727e9c52e03b83b94406bdcc95fc",
+    "ebc3959ab7801a1df6bac4fa7d970652f1df76b683cd2f4003c941c63d517e59",
+    "d392c49fdb700a24cd1fceb237c1f65dcc128f6b34a8aacb58b59384b5c648c2",
+    "2685e9c194c82ae67e10ba59d9ea5345a23dc093e92276fc5361f6667d79cd3f",
+    "46846a5a0139d1e3cb77293e521c2865f7bcdb82c44e8d0a06a2cd0ecba48c0b",
+    "2eed0aef492291e061633d7ad8117f1a2b03eb80a29d0e4e3117ac2528d05ffd",
+    "9e95f86c167de88f450f0aaf89e87f6624a57f973c67b516e338e8e8b8897f60",
+    "5c5942792bd8340cb8b27cd592f1015edf56a8c5b26276ee18a482428e7c5726",
+    "417b26b3d8500a4ae3d59717d7011952db6fc2fb84b807f3f94ac734e89c1b5f",
+];
+
+// a named self-test, true if it passed
+type SelfTest = (&'static str, fn() -> bool);
+
+// Run all the self-tests and refuse to run if any of them fails
+pub fn selftest() -> Result<(), ScramblerError> {
+    let tests: [SelfTest; 6] = [
+        ("SHA3-512", sha3test),
+        ("Argon2id", argon2test),
+        ("key derivation", kdftest),
+        ("key division", dividekeytest),
+        ("word scrambling", scrambletest),
+        ("wordlists", wordliststest),
+    ];
+
+    print!("\nRunning self-tests...");
+    for (name, test) in tests {
+        if !test() {
+            println!(" FAILED");
+            return Err(ScramblerError::SelfTest(name));
+        }
+    }
+    println!(" passed");
+    Ok(())
+}
+
+// SHA3-512 known answers, and iterations hashing the previous hash
+fn sha3test() -> bool {
+    hex(&sha3(b"abc", 1)) == SHA3_ABC
+        && hex(&sha3(b"", 1)) == SHA3_EMPTY
+        && sha3(b"abc", 2) == sha3(&sha3(b"abc", 1), 1)
+        && sha3(b"abc", 0) == b"abc"
+}
+
+// Argon2id known answers with our multi threaded implementation
+fn argon2test() -> bool {
+    ARGON2ID.iter().all(|&(lanes, expected)| {
+        let params = Params::new(256, 2, lanes, Some(32)).expect("Failed to create Argon2 parameters");
+        let mut blocks = vec![Block::new(); params.block_count()];
+        let mut output = [0u8; 32];
+        hash_password_parallel(&params, b"password", b"somesalt", &mut output, &mut blocks).is_ok()
+            && hex(&output) == expected
+    })
+}
+
+// The key derivation logic (salts, labels, salt words, chaining, cascade) with a reduced cost
+fn kdftest() -> bool {
+    KDF_VECTORS.iter().all(|&(kdf, label, salt, expected)| {
+        derive_key_with(KDF_PASSWORD, label.as_bytes(), salt, kdf, &TEST_COST, None)
+            .is_ok_and(|key| hex(&*key) == expected)
+    })
+}
+
+// The key is divided into chunks LSB first
+fn dividekeytest() -> bool {
+    let mut key = [0u8; 64];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = (i * 37 + 11) as u8;
+    }
+    dividekey(&key, MAX_WORDS, 11).is_ok_and(|chunks| chunks == DIVIDE_11)
+        && dividekey(&key, MAX_WORDS, 10).is_ok_and(|chunks| chunks == DIVIDE_10)
+}
+
+// Scrambling twice with the same key gives back the words, for every language
+fn scrambletest() -> bool {
+    let mut key = [0u8; 64];
+    key.copy_from_slice(&sha3(b"scramble self-test", 1));
+
+    (0..WORDS.len()).all(|lang| {
+        let count = 1 << getwordlistbitsize(lang);
+        let words: Vec<usize> = (0..MAX_WORDS).map(|i| (i * 97 + lang) % count).collect();
+        scramblewords(&words, &key, lang).is_ok_and(|scrambled| {
+            scrambled.iter().all(|&word| !WORDS[lang][word].is_empty())
+                && scramblewords(&scrambled, &key, lang).is_ok_and(|again| again == words)
+        })
+    })
+}
+
+// The wordlists are the published ones, hashed in the format of the published files
+// and the SLIP39 list has the properties SLIP-0039 guarantees: 1024 sorted words of 4 to 8 letters,
+// no two of them starting with the same 4 letters
+fn wordliststest() -> bool {
+    let digests = WORDS.iter().zip(WORDLIST_DIGESTS).all(|(words, expected)| {
+        let mut hasher = Sha256::new();
+        for word in words.iter().filter(|word| !word.is_empty()) {
+            hasher.update(word.as_bytes());
+            hasher.update(b"\n");
+        }
+        hex(&hasher.finalize()) == expected
+    });
+
+    let slip39: Vec<&str> = WORDS[0].iter().copied().filter(|word| !word.is_empty()).collect();
+    let mut prefixes: Vec<&str> = slip39.iter().map(|word| &word[..word.len().min(4)]).collect();
+    prefixes.dedup();
+    digests
+        && slip39.len() == 1024
+        && slip39.windows(2).all(|pair| pair[0] < pair[1])
+        && slip39.iter().all(|word| (4..=8).contains(&word.len()) && word.bytes().all(|b| b.is_ascii_lowercase()))
+        && prefixes.len() == 1024
+}
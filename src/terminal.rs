@@ -0,0 +1,102 @@
+// Module: terminal
+// The terminal the interactive flow talks to, the console when running
+// and a scripted one in the tests so the whole flow can run without a terminal
+//
+use crate::error::ScramblerError;
+use std::io::{self, Write};
+use zeroize::Zeroizing;
+
+pub trait Terminal {
+    // Show text to the user (without a new line)
+    fn print(&mut self, text: &str);
+
+    // Read a line from the user (without the line ending)
+    fn readline(&mut self) -> Result<String, ScramblerError>;
+
+    // Show a line of text to the user
+    fn println(&mut self, text: &str) {
+        self.print(text);
+        self.print("\n");
+    }
+}
+
+// The real terminal: stdin and stdout
+// printing can't fail the flow, a failed write is reported by the next read (nothing is read without its question)
+#[derive(Default)]
+pub struct Console {
+    failed: Option<io::Error>,
+}
+
+impl Terminal for Console {
+    fn print(&mut self, text: &str) {
+        if self.failed.is_none() {
+            let mut stdout = io::stdout().lock();
+            if let Err(e) = stdout.write_all(text.as_bytes()).and_then(|_| stdout.flush()) {
+                self.failed = Some(e);
+            }
+        }
+    }
+
+    fn readline(&mut self) -> Result<String, ScramblerError> {
+        if let Some(e) = self.failed.take() {
+            return Err(ScramblerError::Io("write to the terminal".to_string(), e));
+        }
+        // the line can be the password or the words: wiped unless it is returned
+        // (big enough for any answer, a buffer that grows leaves copies behind)
+        let mut input = Zeroizing::new(String::with_capacity(1024));
+        match io::stdin().read_line(&mut input) {
+            // the input was closed, asking again would loop forever
+            Ok(0) => Err(ScramblerError::Io(
+                "read the input".to_string(),
+                io::Error::new(io::ErrorKind::UnexpectedEof, "the input was closed"),
+            )),
+            Ok(_) => {
+                let len = input.trim_end_matches(['\r', '\n']).len();
+                input.truncate(len);
+                Ok(std::mem::take(&mut *input))
+            }
+            Err(e) => Err(ScramblerError::Io("read the input".to_string(), e)),
+        }
+    }
+}
+
+// A scripted terminal for the tests: reads the given lines and records everything shown
+#[cfg(test)]
+pub struct Script {
+    input: std::collections::VecDeque<String>,
+    pub output: String,
+}
+
+#[cfg(test)]
+impl Script {
+    pub fn new(lines: &[&str]) -> Self {
+        Script {
+            input: lines.iter().map(|line| line.to_string()).collect(),
+            output: String::new(),
+        }
+    }
+
+    // Number of lines the flow didn't read
+    pub fn unread(&self) -> usize {
+        self.input.len()
+    }
+}
+
+#[cfg(test)]
+impl Terminal for Script {
+    fn print(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn readline(&mut self) -> Result<String, ScramblerError> {
+        match self.input.pop_front() {
+            Some(line) => {
+                // echo the input like a terminal would
+                self.output.push_str(&line);
+                self.output.push('\n');
+                Ok(line)
+            }
+            None => panic!("The script ran out of input, output so far:\n{}", self.output),
+        }
+    }
+}
@@ -0,0 +1,515 @@
+// Module: tests
+// Golden vectors: any change to these results makes existing backups unrecoverable
+// and regression tests of the bugs found by the fuzz targets
+// the vectors use the reduced test cost, except the full cost one (run with: cargo test --release -- --ignored)
+//
+use crate::constants::*;
+use crate::crypto::*;
+use crate::files::*;
+use crate::lanes::*;
+use crate::parse::*;
+use crate::romanize::*;
+use crate::utils::*;
+use crate::walletfile::*;
+use crate::wordlists::pinyin::{PINYIN_READINGS, TRADITIONAL_READINGS};
+use crate::wordlists::WORDS;
+
+use argon2::{Algorithm, Argon2, Block, Params, Version};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use unicode_normalization::UnicodeNormalization;
+
+// language|scheme|label|salt words|password|words|scrambled words
+const GOLDEN: &str = include_str!("../testdata/golden.txt");
+
+struct Vector {
+    lang: usize,
+    kdf: usize,
+    label: String,
+    salt: Vec<usize>,
+    password: String,
+    words: Vec<usize>,
+    scrambled: Vec<usize>,
+}
+
+// Look up space separated words in a wordlist
+fn indexes(words: &str, lang: usize) -> Vec<usize> {
+    words
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            WORDS[lang]
+                .iter()
+                .position(|&w| !w.is_empty() && w == word)
+                .unwrap_or_else(|| panic!("{} is not in wordlist {}", word, lang))
+        })
+        .collect()
+}
+
+fn vectors() -> Vec<Vector> {
+    GOLDEN
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<&str> = line.split('|').collect();
+            assert_eq!(fields.len(), 7, "bad golden vector: {}", line);
+            let lang = fields[0].parse().unwrap();
+            Vector {
+                lang,
+                kdf: fields[1].parse().unwrap(),
+                label: fields[2].to_string(),
+                salt: indexes(fields[3], lang),
+                password: fields[4].to_string(),
+                words: indexes(fields[5], lang),
+                scrambled: indexes(fields[6], lang),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn golden_vectors() {
+    let vectors = vectors();
+
+    // every language and every word count is covered
+    for lang in 0..WORDS.len() {
+        for count in MIN_WORDS..=MAX_WORDS {
+            assert!(
+                vectors.iter().any(|v| v.lang == lang && v.words.len() == count),
+                "no golden vector for language {} with {} words",
+                lang,
+                count
+            );
+        }
+    }
+
+    // vectors sharing a password, label, salt and scheme share the key, derive it once
+    let mut keys: HashMap<(String, String, Vec<usize>, usize), [u8; 64]> = HashMap::new();
+
+    for v in &vectors {
+        let key = &*keys
+            .entry((v.password.clone(), v.label.clone(), v.salt.clone(), v.kdf))
+            .or_insert_with(|| *derive_key_with(v.password.as_bytes(), v.label.as_bytes(), &v.salt, v.kdf, &TEST_COST, None).unwrap());
+
+        assert_eq!(
+            scramblewords(&v.words, key, v.lang).unwrap(),
+            v.scrambled,
+            "scrambling language {} with {} words",
+            v.lang,
+            v.words.len()
+        );
+        assert_eq!(
+            scramblewords(&v.scrambled, key, v.lang).unwrap(),
+            v.words,
+            "recovering language {} with {} words",
+            v.lang,
+            v.words.len()
+        );
+    }
+}
+
+#[test]
+fn lanes_match_the_argon2_crate() {
+    // every number of lanes with the smallest memory, memory sizes that are not a multiple of 4 lanes
+    // (the extra blocks are not used), one to three passes, short and long (H' chained) outputs,
+    // with the scalar and the AVX2 compression (when the CPU has it)
+    for (name, compress) in compressors() {
+        for lanes in 1..=4u32 {
+            for (memory, passes, length) in [(8 * lanes, 1, 32), (37, 2, 64), (101, 3, 100), (258, 1, 16), (513, 2, 32)] {
+                let memory = memory.max(8 * lanes);
+                let params = Params::new(memory, passes, lanes, Some(length)).unwrap();
+                let password = format!("password {} {}", lanes, memory);
+
+                let mut expected = vec![0u8; length];
+                let mut blocks = vec![Block::new(); params.block_count()];
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+                    .hash_password_into_with_memory(password.as_bytes(), b"somesalt", &mut expected, &mut blocks)
+                    .unwrap();
+
+                let mut output = vec![0u8; length];
+                let mut blocks = vec![Block::new(); params.block_count()];
+                hash_password_with(&params, password.as_bytes(), b"somesalt", &mut output, &mut blocks, compress).unwrap();
+                assert_eq!(output, expected, "{}: {} lanes, {} KiB, {} passes", name, lanes, memory, passes);
+            }
+        }
+    }
+
+    // the one used by the program
+    let params = Params::new(64, 1, 4, Some(32)).unwrap();
+    let (mut expected, mut output) = ([0u8; 32], [0u8; 32]);
+    let mut blocks = vec![Block::new(); params.block_count()];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+        .hash_password_into_with_memory(b"password", b"somesalt", &mut expected, &mut blocks)
+        .unwrap();
+    hash_password_parallel(&params, b"password", b"somesalt", &mut output, &mut blocks).unwrap();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn self_tests_pass() {
+    crate::selftest::selftest().unwrap();
+}
+
+#[test]
+fn dividekey_is_lsb_first() {
+    // the first chunk takes the lowest bits of the first byte, and continues into the next byte
+    let mut key = [0u8; 64];
+    key[0] = 0b1010_0101;
+    key[1] = 0b0000_0110;
+    assert_eq!(dividekey(&key, 2, 11).unwrap()[..2], [0b110_1010_0101, 0]);
+    assert_eq!(dividekey(&key, 2, 10).unwrap()[..2], [0b10_1010_0101, 0b1]);
+
+    // all 64 bytes are used by the largest wallet: 33 chunks of 11 bits need 363 bits
+    let key = [0xff; 64];
+    assert!(dividekey(&key, MAX_WORDS, 11).unwrap().iter().all(|&chunk| chunk == 0x7ff));
+    assert!(dividekey(&key, MAX_WORDS, 10).unwrap().iter().all(|&chunk| chunk == 0x3ff));
+}
+
+#[test]
+fn dividekey_rejects_bad_sizes() {
+    let key = [0u8; 64];
+    let message = |parts, chunksize| dividekey(&key, parts, chunksize).unwrap_err().to_string();
+    assert!(message(MAX_WORDS, 0).starts_with("Invalid chunk size"));
+    assert!(message(MAX_WORDS, 17).starts_with("Invalid chunk size"));
+    assert!(message(0, 17).starts_with("Invalid chunk size"));
+    // 64 bytes only have 512 bits
+    assert!(message(47, 11).starts_with("The key is too short"));
+    assert!(dividekey(&key, 0, 11).unwrap().is_empty());
+}
+
+#[test]
+fn scrambled_words_stay_in_the_wordlist() {
+    // the scrambled index is taken modulo the wordlist table size (2048), SLIP39 only fills
+    // the first 1024 entries so its 10 bit results must never reach the empty entries
+    let key = [0xff; 64];
+    for (lang, wordlist) in WORDS.iter().enumerate() {
+        let words: Vec<usize> = (0..MAX_WORDS).collect();
+        let scrambled = scramblewords(&words, &key, lang).unwrap();
+        let size = 1 << getwordlistbitsize(lang);
+        assert!(scrambled.iter().all(|&word| word < size && !wordlist[word].is_empty()));
+    }
+    assert_eq!(getwordlistbitsize(0), 10);
+    assert!((1..WORDS.len()).all(|lang| getwordlistbitsize(lang) == 11));
+}
+
+// v1 with the full cost, English, no label and no salt words (the original scrambler)
+const FULL_PASSWORD: &str = "Correct-Horse-Battery-Staple-2024";
+const FULL_WORDS: &str = "cricket length smile bone grass raw you envelope observe topic coyote layer \
+    slab bleak goddess radio word energy note today copper lady since beyond";
+const FULL_SCRAMBLED: &str = "glory fresh custom bachelor stem obvious ignore swarm forget found remember boost \
+    aim announce board uniform hour salute fever carry daring funny bar fever";
+
+#[test]
+#[ignore = "full cost key derivation, takes minutes and needs 2 GiB of memory"]
+fn golden_vector_full_cost() {
+    let lang = 1;
+    let words = indexes(FULL_WORDS, lang);
+    let key = derive_key_with(FULL_PASSWORD.as_bytes(), b"", &[], KDF_V1, &FULL_COST, None).unwrap();
+    assert_eq!(scramblewords(&words, &key, lang).unwrap(), indexes(FULL_SCRAMBLED, lang));
+}
+
+#[test]
+fn suggestions_for_multi_byte_input() {
+    // the 4 letter prefix used to be sliced by bytes, which panics in the middle of a letter
+    assert!(find_suggestions("aéé", 3).len() <= 3);
+    assert_eq!(find_suggestions("あいこく", 7)[0], "あいこくしん");
+
+    // never the empty entries padding the SLIP39 list, never the same word twice
+    let suggestions = find_suggestions("ab", 0);
+    assert!(suggestions.iter().all(|word| !word.is_empty()));
+    let suggestions = find_suggestions("abandn", 1);
+    assert_eq!(suggestions.iter().filter(|&word| word == "abandon").count(), 1);
+}
+
+#[test]
+fn empty_words_are_not_slip39_words() {
+    assert_eq!(findword("", 0), None);
+    assert_eq!(parsewalletfile(&"\n".repeat(20)), Err(ParseError::FileSize(0)));
+}
+
+#[test]
+fn wallet_files() {
+    let english = "abandon ability able about above absent absorb abstract absurd abuse access accident";
+    assert_eq!(parsewalletfile(&english.replace(' ', "\n")), Ok(vec![(1, (0..12).collect())]));
+    assert_eq!(parsewalletfile(&english.replace(' ', "\r\n")), Ok(vec![(1, (0..12).collect())]));
+
+    // these words are in the SLIP39, English and French lists
+    let shared = "animal aspect brave civil client crucial distance dragon exact excuse fatal fatigue";
+    let found = parsewalletfile(shared).unwrap();
+    assert_eq!(found.iter().map(|(lang, _)| *lang).collect::<Vec<_>>(), [0, 1, 3]);
+    assert!(found.iter().all(|(lang, words)| words.iter().map(|&word| WORDS[*lang][word]).eq(shared.split(' '))));
+    assert_eq!(parsewalletfile("abandon\n"), Err(ParseError::FileSize(1)));
+    assert_eq!(parsewalletfile(&"nope\n".repeat(12)), Err(ParseError::UnknownLanguage));
+    assert_eq!(parsechoice(" 2 ", 2), Ok(1));
+    assert_eq!(parsechoice("0", 2), Err(ParseError::Choice));
+    assert_eq!(parsewalletsize("34"), Err(ParseError::WalletSize));
+}
+
+#[test]
+fn json_wallet_files() {
+    let info = WalletInfo {
+        kdf: KDF_CASCADE,
+        profile: "full".to_string(),
+        label: "family-vault-2".to_string(),
+        saltwords: SALT_WORDS,
+        created: "2026-10-19".to_string(),
+    };
+    let words: Vec<usize> = (100..114).collect();
+    let file = encodewallet(3, &words, &info);
+    assert!(iswalletjson(&file) && !iswalletjson("abandon\n"));
+    assert_eq!(decodewallet(&file), Ok((3, words.clone(), info.clone())));
+    assert_eq!(decodewallet(&format!("\u{feff}{}", file)), Ok((3, words, info.clone())));
+
+    // a file an editor converted to NFC is the same wallet ("académie" is written decomposed)
+    let academie = findword(&"académie".nfkd().collect::<String>(), 3).unwrap();
+    let accented: Vec<usize> = (academie..academie + 14).collect();
+    let written = encodewallet(3, &accented, &info);
+    let nfc: String = written.nfc().collect();
+    assert_ne!(nfc, written);
+    assert_eq!(decodewallet(&nfc), Ok((3, accented, info)));
+
+    // every field is checked
+    let damaged = |from: &str, to: &str| decodewallet(&file.replacen(from, to, 1));
+    assert_eq!(damaged("\"v3\"", "\"v9\""), Err(ParseError::Field("scheme")));
+    assert_eq!(damaged("\"french\"", "\"klingon\""), Err(ParseError::Field("language")));
+    assert_eq!(damaged("\"version\": 1", "\"version\": 2"), Err(ParseError::Version(2)));
+    assert_eq!(damaged("catsec-scrambler-wallet", "other"), Err(ParseError::Format));
+    assert_eq!(damaged("2026-10-19", "2026-13-19"), Err(ParseError::Field("creation date")));
+    assert_eq!(damaged("\"word_count\": 12", "\"word_count\": 13"), Err(ParseError::Field("word count")));
+    assert_eq!(damaged("family-vault-2", "family/vault"), Err(ParseError::Field("label")));
+    assert_eq!(damaged("family-vault-2", "family-vault-3"), Err(ParseError::Checksum));
+    assert_eq!(damaged(&format!("\"{}\"", WORDS[3][100]), "\"nope\""), Err(ParseError::UnknownWord(1)));
+    assert!(matches!(damaged("\"created\"", "\"extra\": 1, \"created\""), Err(ParseError::Json(_))));
+
+    assert_eq!(civildate(0), (1970, 1, 1));
+    assert_eq!(civildate(11016), (2000, 2, 29));
+    assert_eq!(civildate(20745), (2026, 10, 19));
+}
+
+#[test]
+fn removable_drives() {
+    // a fake /sys/block: sdb and mmcblk0 are removable, sda is not
+    let sysblock = std::env::temp_dir().join(format!("scrambler-sysblock-{}", std::process::id()));
+    for (device, removable) in [("sda", "0\n"), ("sdb", "1\n"), ("mmcblk0", "1\n")] {
+        std::fs::create_dir_all(sysblock.join(device)).unwrap();
+        std::fs::write(sysblock.join(device).join("removable"), removable).unwrap();
+    }
+    let devices = removabledevices(&sysblock);
+    std::fs::remove_dir_all(&sysblock).unwrap();
+    assert_eq!(devices, ["mmcblk0", "sdb"]);
+
+    let mounts = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+        /dev/sdb1 /media/user/MY\\040USB vfat rw,nosuid 0 0\n\
+        /dev/sdb2 /media/user/live iso9660 ro,nosuid 0 0\n\
+        /dev/mmcblk0p1 /mnt/sd exfat rw 0 0\n\
+        /dev/sdba1 /mnt/other ext4 rw 0 0\n\
+        tmpfs /tmp tmpfs rw 0 0\n";
+    assert_eq!(
+        removablemounts(mounts, &devices),
+        [PathBuf::from("/media/user/MY USB"), PathBuf::from("/mnt/sd")]
+    );
+
+    // a live system booted from the drive
+    assert!(removablemounts("/dev/sdb1 / ext4 rw 0 0\n", &devices).is_empty());
+}
+
+#[test]
+fn atomic_writes_never_replace_silently() {
+    let dir = std::env::temp_dir().join(format!("scrambler-atomic-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let wallet = dir.join("wallet.json");
+
+    // a new file is created, an existing one (created after the user was asked) is left alone
+    writeatomic(&wallet, b"first", false).unwrap();
+    let e = writeatomic(&wallet, b"second", false).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(std::fs::read(&wallet).unwrap(), b"first");
+
+    // it is only replaced when the user agreed, and no temporary file is left behind
+    writeatomic(&wallet, b"second", true).unwrap();
+    assert_eq!(std::fs::read(&wallet).unwrap(), b"second");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn wiped_files_are_gone() {
+    let dir = std::env::temp_dir().join(format!("scrambler-wipe-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let draft = dir.join("draft.txt");
+    std::fs::write(&draft, "peanut update depth march ".repeat(5000)).unwrap();
+
+    // a hard link shares the data: it is overwritten in place, not only unlinked
+    std::fs::hard_link(&draft, dir.join("link")).unwrap();
+    wipefile(&draft).unwrap();
+    assert!(!draft.exists());
+    assert_eq!(std::fs::read(dir.join("link")).unwrap(), b"");
+
+    // directories and missing files are refused
+    assert!(wipefile(&dir).is_err());
+    assert!(wipefile(&draft).is_err());
+    #[cfg(unix)]
+    {
+        // a link is not followed
+        std::fs::write(&draft, "words").unwrap();
+        std::os::unix::fs::symlink(&draft, dir.join("symlink")).unwrap();
+        assert!(wipefile(&dir.join("symlink")).is_err());
+        assert_eq!(std::fs::read_to_string(&draft).unwrap(), "words");
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn word_prefixes() {
+    // the first 4 letters are always enough in SLIP39 and English
+    for lang in [0, 1] {
+        for (index, word) in WORDS[lang].iter().enumerate().filter(|(_, word)| !word.is_empty()) {
+            let prefix: String = word.chars().take(4).collect();
+            assert_eq!(resolveword(&prefix, lang), Some(index), "{}", word);
+        }
+    }
+
+    // a word that starts other words is that word, a prefix of several words is not resolved
+    assert_eq!(resolveword("act", 1), findword("act", 1));
+    assert_eq!(resolveword("acti", 1), findword("action", 1));
+    assert_eq!(resolveword("ab", 1), None);
+    assert_eq!(resolveword("", 0), None);
+
+    // pasted words and files
+    assert_eq!(parsewords("aban abil 3. able", 1), Ok(vec![0, 1, 2]));
+    let plate = "aban abil able abou abov abse abso abst absu abus acce acci";
+    assert!(parsewalletfile(plate).unwrap().contains(&(1, (0..12).collect())));
+}
+
+#[test]
+fn accents_and_normal_forms() {
+    // French: composed (NFC) or decomposed (NFKD), without accents, in capitals, as a prefix
+    let elephant = findword("e\u{301}le\u{301}phant", 3).unwrap();
+    for typed in ["\u{e9}l\u{e9}phant", "e\u{301}le\u{301}phant", "elephant", "ELEPHANT", "\u{e9}l\u{e9}p", "elep"] {
+        assert_eq!(parsewords(typed, 3), Ok(vec![elephant]), "{:?}", typed);
+    }
+    assert!(find_suggestions("elephnt", 3).contains(&WORDS[3][elephant].to_string()));
+
+    // Japanese and Korean typed in NFC (the voicing marks of Japanese are not folded away)
+    for lang in [7, 8] {
+        for (index, word) in WORDS[lang].iter().enumerate().step_by(97) {
+            assert_eq!(parsewords(&word.nfc().collect::<String>(), lang), Ok(vec![index]));
+        }
+    }
+    assert_ne!(foldword("\u{304c}"), foldword("\u{304b}"));
+
+    // no two words of a list are the same without accents
+    for list in WORDS.iter() {
+        let mut folded: Vec<String> = list.iter().filter(|word| !word.is_empty()).map(|word| foldword(word)).collect();
+        let count = folded.len();
+        folded.sort();
+        folded.dedup();
+        assert_eq!(folded.len(), count);
+    }
+}
+
+// The words and their positions
+fn tokens(text: &str) -> Vec<(String, usize, usize)> {
+    tokenize(text).iter().map(|token| (token.word.clone(), token.line, token.column)).collect()
+}
+
+#[test]
+fn mnemonic_formats() {
+    let words = |text: &str| tokenize(text).iter().map(|token| token.word.clone()).collect::<Vec<_>>();
+    let phrase = ["abandon", "ability", "able"];
+    for text in [
+        "abandon ability able",
+        "abandon, ability,able",
+        "abandon\nability\r\nable\n\n",
+        "1. abandon\n2. ability\n3. able",
+        "01 abandon 02 ability 03 able",
+        "1) Abandon 2) ABILITY 3)able",
+        "\u{feff}abandon   ability\table  ",
+        "abandon\u{3000}ability\u{3000}able",
+    ] {
+        assert_eq!(words(text), phrase, "{:?}", text);
+    }
+
+    // the positions are in characters, after the numbering
+    assert_eq!(
+        tokens("1. éléphant, 2. abandn\n  3.nope"),
+        [("e\u{301}le\u{301}phant".to_string(), 1, 4), ("abandn".to_string(), 1, 17), ("nope".to_string(), 2, 5)]
+    );
+
+    // every unknown word is reported with its position
+    let Err(ParseError::UnknownWords(1, unknown)) = parsewords("abandon abandn ability\nnope", 1) else {
+        panic!("the unknown words are not reported");
+    };
+    let unknown: Vec<_> = unknown.iter().map(|(i, token)| (*i, token.word.as_str(), token.line, token.column)).collect();
+    assert_eq!(unknown, [(2, "abandn", 1, 9), (4, "nope", 2, 1)]);
+}
+
+#[test]
+fn romanized_words() {
+    let word = |typed: &str, lang: usize| resolveword(typed, lang).map(|index| WORDS[lang][index].nfc().collect::<String>());
+
+    // romaji: Hepburn, Kunrei or as typed in an input method
+    for typed in ["tsuzuku", "tuzuku", "tudzuku"] {
+        assert_eq!(word(typed, LANG_JAPANESE).as_deref(), Some("つづく"), "{:?}", typed);
+    }
+    for (typed, kana) in [("akachan", "あかちゃん"), ("akatyan", "あかちゃん"), ("itchi", "いっち"), ("itti", "いっち"), ("an'i", "あんい"), ("ani", "あんい"), ("anngai", "あんがい")] {
+        assert_eq!(word(typed, LANG_JAPANESE).as_deref(), Some(kana), "{:?}", typed);
+    }
+
+    // the start of a reading is only a match when typed (the user picks the word), never in a file
+    let aikokushin = findword(&"あいこくしん".nfkd().collect::<String>(), LANG_JAPANESE).unwrap();
+    assert_eq!(readingmatches("aiko", LANG_JAPANESE), [aikokushin]);
+    assert!(readingwords("aiko", LANG_JAPANESE).is_empty());
+    assert_eq!(resolveword("aiko", LANG_JAPANESE), None);
+    let mut file: Vec<&str> = WORDS[LANG_JAPANESE][..11].to_vec();
+    file.push("aiko");
+    assert!(parsewalletfile(&file.join("\n")).is_err());
+    file[11] = "aikokushin";
+    assert_eq!(parsewalletfile(&file.join("\n")).unwrap()[0].1[11], aikokushin);
+
+    // revised romanization and pinyin, with tone marks or numbers, u: or ü as v
+    assert_eq!(word("gagyeok", LANG_KOREAN).as_deref(), Some("가격"));
+    assert_eq!(word("him-kkeot", LANG_KOREAN).as_deref(), Some("힘껏"));
+    for lang in [LANG_CHINESE_SIMPLIFIED, LANG_CHINESE_TRADITIONAL] {
+        for typed in ["neng2", "néng", "neng"] {
+            assert_eq!(resolveword(typed, lang), Some(43), "{:?}", typed);
+        }
+    }
+    assert_eq!(word("nü", LANG_CHINESE_SIMPLIFIED).as_deref(), Some("女"));
+    assert_eq!(word("nu:3", LANG_CHINESE_SIMPLIFIED).as_deref(), Some("女"));
+
+    // a reading of several words is not resolved, the user picks one of them
+    assert_eq!(resolveword("shi4", LANG_CHINESE_SIMPLIFIED), None);
+    assert!(readingmatches("shi4", LANG_CHINESE_SIMPLIFIED).contains(&findword("是", LANG_CHINESE_SIMPLIFIED).unwrap()));
+    assert!(readingmatches("shi", LANG_CHINESE_SIMPLIFIED).len() > readingmatches("shi4", LANG_CHINESE_SIMPLIFIED).len());
+    assert!(readingmatches("abandon", 1).is_empty());
+
+    // every word is found from the romanization it is shown with
+    for lang in [LANG_JAPANESE, LANG_KOREAN, LANG_CHINESE_SIMPLIFIED, LANG_CHINESE_TRADITIONAL] {
+        for (index, word) in WORDS[lang].iter().enumerate() {
+            let reading = romanized(index, lang).unwrap();
+            assert!(readingmatches(&reading, lang).contains(&index), "{} {}", word, reading);
+        }
+    }
+    assert_eq!(romanized(findword(&"あんい".nfkd().collect::<String>(), LANG_JAPANESE).unwrap(), LANG_JAPANESE).as_deref(), Some("an'i"));
+
+    // the traditional 矽 is read xi4 (Taiwan), the simplified 硅 at the same index gui1
+    let silicon = findword("矽", LANG_CHINESE_TRADITIONAL).unwrap();
+    assert_eq!(findword("硅", LANG_CHINESE_SIMPLIFIED), Some(silicon));
+    assert!(readingwords("xi4", LANG_CHINESE_TRADITIONAL).contains(&silicon));
+    assert!(!readingwords("xi4", LANG_CHINESE_SIMPLIFIED).contains(&silicon));
+    assert_eq!(romanized(silicon, LANG_CHINESE_SIMPLIFIED).as_deref(), Some("gui1"));
+
+    // the pinyin readings are syllables with a tone number (5 is the neutral tone), v for ü (only after l and n)
+    for (index, readings) in PINYIN_READINGS.iter().chain(TRADITIONAL_READINGS.iter().map(|(_, readings)| readings)).enumerate() {
+        let readings: Vec<&str> = readings.split(' ').collect();
+        for (i, reading) in readings.iter().enumerate() {
+            let (syllable, tone) = reading.split_at(reading.len() - 1);
+            assert!(!syllable.is_empty() && syllable.bytes().all(|c| c.is_ascii_lowercase()), "{} {}", index, reading);
+            assert!(("1"..="5").contains(&tone), "{} {}", index, reading);
+            assert!(!syllable.contains('v') || ["lv", "nv"].iter().any(|start| syllable.starts_with(start)), "{} {}", index, reading);
+            assert!(!readings[..i].contains(reading), "{} {}", index, reading);
+        }
+    }
+    assert!(closestreadings("gagyok", LANG_KOREAN).contains(&0));
+}
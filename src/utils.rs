@@ -3,24 +3,119 @@
 //
 
 use crate::constants::*;
+use crate::error::ScramblerError;
+use crate::parse::{foldedwords, foldword};
+use crate::terminal::Terminal;
 use levenshtein::levenshtein;
 use std::net::TcpStream;
+use zeroize::Zeroizing;
 
 // Check if the user is connected to the internet
 pub fn internetconnection() -> bool {
     TcpStream::connect("8.8.8.8:53").is_ok() // Google's public DNS
 }
 
+// Get the memory available to the program in bytes, the smallest of the system's available
+// memory and the cgroup (container) limits, None if it can't be read (only Linux is supported)
+pub fn availablememory() -> Option<u64> {
+    let system = meminfo();
+    let cgroup = cgroupmemory();
+    match (system, cgroup) {
+        (Some(system), Some(cgroup)) => Some(system.min(cgroup)),
+        _ => system.or(cgroup),
+    }
+}
+
+// Read the available memory from /proc/meminfo
+fn meminfo() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemAvailable:"))?;
+
+    // the value is in kB
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+// Read the memory left under the cgroup limits of the program (cgroup v1 and v2)
+fn cgroupmemory() -> Option<u64> {
+    let cgroups = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let mut available: Option<u64> = None;
+
+    for line in cgroups.lines() {
+        // lines look like "4:memory:/path" (v1) or "0::/path" (v2)
+        let mut parts = line.splitn(3, ':');
+        let (id, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+        let (root, limitfile, usagefile) = if id == "0" && controllers.is_empty() {
+            ("/sys/fs/cgroup", "memory.max", "memory.current")
+        } else if controllers.split(',').any(|c| c == "memory") {
+            ("/sys/fs/cgroup/memory", "memory.limit_in_bytes", "memory.usage_in_bytes")
+        } else {
+            continue;
+        };
+
+        // the limits of the parent cgroups apply too, so check every level up to the root
+        let mut dir = format!("{}{}", root, path.trim_end_matches('/'));
+        loop {
+            let limit = readnumber(&format!("{}/{}", dir, limitfile));
+            let usage = readnumber(&format!("{}/{}", dir, usagefile)).unwrap_or(0);
+
+            // "max" (v2) or a huge value (v1) means there is no limit
+            if let Some(limit) = limit.filter(|&limit| limit < 1 << 60) {
+                let left = limit.saturating_sub(usage);
+                available = Some(available.map_or(left, |a| a.min(left)));
+            }
+
+            if dir == root {
+                break;
+            }
+            match dir.rfind('/') {
+                Some(index) if index >= root.len() => dir.truncate(index),
+                _ => break,
+            }
+        }
+    }
+    available
+}
+
+// Read a number from a file
+fn readnumber(path: &str) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// Format a number of bytes as GiB
+pub fn gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+// Format bytes as hex
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
 // Divide the key into chunks of the specified size
-pub fn dividekey(data: [u8; 64], parts: usize, chunksize: usize) -> Vec<u16> {
+pub fn dividekey(data: &[u8; 64], parts: usize, chunksize: usize) -> Result<Vec<u16>, ScramblerError> {
     // Calculate the total number of bits required for the chunks
     let totalbits = parts * chunksize;
 
+    // chunks are 1 to 16 bits
+    if chunksize == 0 || chunksize > 16 {
+        return Err(ScramblerError::Input(format!(
+            "Invalid chunk size of {} bits, chunks are 1 to 16 bits.",
+            chunksize
+        )));
+    }
+
     // Calculate the number of bits available in the key
     let availablebits = data.len() * 8;
     if availablebits < totalbits {
-        // show an error message if the key is too short
-        panic!("Insufficient binary data for the requested chunks and size");
+        // the key is too short for this many words
+        return Err(ScramblerError::Input(format!(
+            "The key is too short for {} words of {} bits.",
+            parts, chunksize
+        )));
+    }
+    if parts == 0 {
+        return Ok(Vec::new());
     }
 
     // Convert the key into a vector of u16 chunks (wordlist is max 11 bits)
@@ -29,7 +124,7 @@ pub fn dividekey(data: [u8; 64], parts: usize, chunksize: usize) -> Vec<u16> {
     let mut bitcounter = 0;
 
     // Iterate over the key data and extract the chunks
-    for &value in &data {
+    for &value in data {
         let mut temp = value;
         for _ in 0..8 {
             // Extract the bits from the byte
@@ -58,36 +153,41 @@ pub fn dividekey(data: [u8; 64], parts: usize, chunksize: usize) -> Vec<u16> {
         }
     }
 
-    // show an error message if we could not generate all the chunks
-    if chunks.len() != parts {
-        panic!("Failed to generate all the chunks");
-    }
-
-    chunks
+    // all the chunks fit in the key (checked above)
+    debug_assert_eq!(chunks.len(), parts);
+    Ok(chunks)
 }
 
 // suggest words based on the user input
-pub fn find_suggestions(word: &str, wordlist: &[&str]) -> Vec<String> {
+// the words are compared without accents (the folded lists are cached), so "elephnt" suggests "éléphant"
+pub fn find_suggestions(word: &str, lang: usize) -> Vec<String> {
     let mut suggestions = Vec::new();
+    let word = Zeroizing::new(foldword(word));
+    let wordlist = &WORDS[lang];
+    let folded = foldedwords(lang);
 
-    // Words that start with the same first 4 letters
-    if word.len() >= 4 {
-        let prefix = &word[..4];
+    // Words that start with the same first 4 letters (letters, not bytes: words can be multi-byte)
+    if word.chars().count() >= 4 {
+        let prefix: String = word.chars().take(4).collect();
         suggestions.extend(
             wordlist
                 .iter()
-                .filter(|&&w| w.starts_with(prefix))
+                .zip(folded)
+                .filter(|(_, f)| f.starts_with(&prefix))
+                .map(|(&w, _)| w)
                 .take(3) // Limit to 3 suggestions
-                .cloned()
                 .map(String::from),
         );
     }
 
     // Words with the smallest Levenshtein distance
     if suggestions.len() < 3 {
+        // skip the empty entries padding the SLIP39 list and the words already suggested
         let mut distances: Vec<(usize, &str)> = wordlist
             .iter()
-            .map(|&w| (levenshtein(word, w), w))
+            .zip(folded)
+            .filter(|(&w, _)| !w.is_empty() && !suggestions.iter().any(|s| s == w))
+            .map(|(&w, f)| (levenshtein(&word, f), w))
             .filter(|&(dist, _)| dist <= 3) // Limit to a maximum distance of 3
             .collect();
 
@@ -119,12 +219,12 @@ pub fn getwordlistbitsize(lang: usize) -> usize {
 }
 
 // Scramble the wallet words using the secret key
-pub fn scramblewords(words: Vec<usize>, secretkey: [u8; 64], lang: usize) -> Vec<usize> {
+pub fn scramblewords(words: &[usize], secretkey: &[u8; 64], lang: usize) -> Result<Vec<usize>, ScramblerError> {
     // Get the number of bits required to represent the word list
     let wordlistbitsize = getwordlistbitsize(lang);
 
-    // Divide the key into chunks of the required size
-    let keychunks = dividekey(secretkey, words.len(), wordlistbitsize);
+    // Divide the key into chunks of the required size (they are key material, wiped when dropped)
+    let keychunks = Zeroizing::new(dividekey(secretkey, words.len(), wordlistbitsize)?);
 
     // prepare a vector to store the new words
     let mut newwords = Vec::with_capacity(words.len());
@@ -140,22 +240,22 @@ pub fn scramblewords(words: Vec<usize>, secretkey: [u8; 64], lang: usize) -> Vec
         // store the new word
         newwords.push(valid_word);
     }
-    newwords
+    Ok(newwords)
 }
 
 // Print the wallet words to the user
-pub fn printwords(words: &[usize], lang: usize, recover: bool) {
+pub fn printwords(term: &mut dyn Terminal, words: &[usize], lang: usize, recover: bool) {
     // change the message based on the action
     if recover {
-        println!("\nRecovered words:\n");
+        term.println("\nRecovered words:\n");
     } else {
-        println!("\nNew words:\n");
+        term.println("\nNew words:\n");
     }
 
     // print the words with their indexes
     for (i, &word) in words.iter().enumerate() {
         // add a space before single digit indexes for better alignment
         let space = if i < 9 { " " } else { "" };
-        println!("{}{}: {}", space, i + 1, WORDS[lang][word]);
+        term.println(&format!("{}{}: {}", space, i + 1, WORDS[lang][word]));
     }
 }
@@ -0,0 +1,188 @@
+// Module: walletfile
+// The JSON wallet file: the scrambled words with everything needed to unscramble them except the password
+// (scheme, cost profile, language, label, salt words), a creation date and a public checksum
+// files of older versions of the program (one word per line) are read by parsewalletfile in parse.rs
+//
+use crate::constants::*;
+use crate::parse::{findword, ParseError};
+use crate::utils::hex;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use unicode_normalization::UnicodeNormalization;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How the words were scrambled, recorded in the wallet file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletInfo {
+    pub kdf: usize,
+    pub profile: String,
+    pub label: String,
+    pub saltwords: usize,
+    pub created: String,
+}
+
+// The file as written, the words are stored as words (not indexes) so the file can be read by a human
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WalletJson {
+    format: String,
+    version: u32,
+    scheme: String,
+    kdf_profile: String,
+    language: String,
+    word_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    created: String,
+    words: Vec<String>,
+    salt: Vec<String>,
+    checksum: String,
+}
+
+// Check if the file is a JSON wallet file (older files are plain words)
+pub fn iswalletjson(contents: &str) -> bool {
+    contents.trim_start_matches('\u{feff}').trim_start().starts_with('{')
+}
+
+// Encode a wallet file, the words are the scrambled words followed by the salt words
+pub fn encodewallet(lang: usize, words: &[usize], info: &WalletInfo) -> String {
+    let (words, salt) = words.split_at(words.len() - info.saltwords);
+    let mut wallet = WalletJson {
+        format: WALLET_FORMAT.to_string(),
+        version: WALLET_VERSION,
+        scheme: KDF_ID[info.kdf].to_string(),
+        kdf_profile: info.profile.clone(),
+        language: LANG_ID[lang].to_string(),
+        word_count: words.len(),
+        label: (!info.label.is_empty()).then(|| info.label.clone()),
+        created: info.created.clone(),
+        words: words.iter().map(|&word| WORDS[lang][word].to_string()).collect(),
+        salt: salt.iter().map(|&word| WORDS[lang][word].to_string()).collect(),
+        checksum: String::new(),
+    };
+    wallet.checksum = checksum(&wallet);
+    serde_json::to_string_pretty(&wallet).expect("Failed to encode the wallet file") + "\n"
+}
+
+// Decode and validate a wallet file, returns the language, the scrambled words followed by the salt words
+// and how they were scrambled
+pub fn decodewallet(contents: &str) -> Result<(usize, Vec<usize>, WalletInfo), ParseError> {
+    let wallet: WalletJson = serde_json::from_str(contents.trim_start_matches('\u{feff}'))
+        .map_err(|e| ParseError::Json(e.to_string()))?;
+
+    if wallet.format != WALLET_FORMAT {
+        return Err(ParseError::Format);
+    }
+    if wallet.version == 0 || wallet.version > WALLET_VERSION {
+        return Err(ParseError::Version(wallet.version));
+    }
+
+    let kdf = KDF_ID.iter().position(|&id| id == wallet.scheme).ok_or(ParseError::Field("scheme"))?;
+    let lang = LANG_ID.iter().position(|&id| id == wallet.language).ok_or(ParseError::Field("language"))?;
+
+    // the profile is checked against the cost of the key derivation when recovering
+    if wallet.kdf_profile.is_empty() || !wallet.kdf_profile.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ParseError::Field("key derivation profile"));
+    }
+
+    // the label can only have the characters allowed when it is entered
+    let label = wallet.label.clone().unwrap_or_default();
+    let allowed = format!("{}{}{}{}", UPPER, LOWER, NUMBERS, LABEL_SPECIAL);
+    if !label.chars().all(|c| allowed.contains(c)) || label.trim() != label {
+        return Err(ParseError::Field("label"));
+    }
+
+    if !isdate(&wallet.created) {
+        return Err(ParseError::Field("creation date"));
+    }
+
+    if wallet.word_count != wallet.words.len() || !(MIN_WORDS..=MAX_WORDS).contains(&wallet.word_count) {
+        return Err(ParseError::Field("word count"));
+    }
+    if !wallet.salt.is_empty() && wallet.salt.len() != SALT_WORDS {
+        return Err(ParseError::Field("number of salt words"));
+    }
+
+    // the words and the salt words, positions start at 1 like in the printed lists
+    // (the file is written NFKD normalized like the wordlists, an editor may have changed that)
+    let words = wallet
+        .words
+        .iter()
+        .chain(&wallet.salt)
+        .enumerate()
+        .map(|(i, word)| findword(&word.nfkd().collect::<String>(), lang).ok_or(ParseError::UnknownWord(i + 1)))
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+
+    if checksum(&wallet) != wallet.checksum {
+        return Err(ParseError::Checksum);
+    }
+
+    let info = WalletInfo {
+        kdf,
+        profile: wallet.kdf_profile,
+        label,
+        saltwords: wallet.salt.len(),
+        created: wallet.created,
+    };
+    Ok((lang, words, info))
+}
+
+// The checksum of everything in the file (not a secret, it only detects damaged files)
+fn checksum(wallet: &WalletJson) -> String {
+    let fields = [
+        wallet.format.clone(),
+        wallet.version.to_string(),
+        wallet.scheme.clone(),
+        wallet.kdf_profile.clone(),
+        wallet.language.clone(),
+        wallet.word_count.to_string(),
+        wallet.label.clone().unwrap_or_default(),
+        wallet.created.clone(),
+        normalized(&wallet.words),
+        normalized(&wallet.salt),
+    ];
+    let mut hasher = Sha3_256::new();
+    hasher.update(fields.join("\n").as_bytes());
+    hex(&hasher.finalize()[..8])
+}
+
+// The words NFKD normalized like the wordlists (the checksum of a file an editor changed to NFC is the same)
+fn normalized(words: &[String]) -> String {
+    words.iter().map(|word| word.nfkd().collect::<String>()).collect::<Vec<_>>().join(" ")
+}
+
+// Check a date is in the YYYY-MM-DD format
+fn isdate(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return false;
+    }
+    if !parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+// Today's date (UTC) as YYYY-MM-DD
+pub fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = civildate(seconds / 86400);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// The date of a number of days since 1970-01-01 (Howard Hinnant's civil_from_days)
+pub fn civildate(days: u64) -> (u64, u64, u64) {
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
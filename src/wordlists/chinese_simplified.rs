@@ -1,5 +1,5 @@
 // Chinese_Simplified BIP39/SLIP39 wordlist
-pub const CHINESE_SIMPLIFIED_WORDS: [&str; 2048] = [
+pub static CHINESE_SIMPLIFIED_WORDS: [&str; 2048] = [
     "的", "一", "是", "在", "不", "了", "有", "和",
     "人", "这", "中", "大", "为", "上", "个", "国",
     "我", "以", "要", "他", "时", "来", "用", "们",
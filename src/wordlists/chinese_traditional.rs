@@ -1,5 +1,5 @@
 // Chinese_Traditional BIP39/SLIP39 wordlist
-pub const CHINESE_TRADITIONAL_WORDS: [&str; 2048] = [
+pub static CHINESE_TRADITIONAL_WORDS: [&str; 2048] = [
     "的", "一", "是", "在", "不", "了", "有", "和",
     "人", "這", "中", "大", "為", "上", "個", "國",
     "我", "以", "要", "他", "時", "來", "用", "們",
@@ -1,5 +1,5 @@
 // Czech BIP39/SLIP39 wordlist
-pub const CZECH_WORDS: [&str; 2048] = [
+pub static CZECH_WORDS: [&str; 2048] = [
     "abdikace", "abeceda", "adresa", "agrese", "akce", "aktovka", "alej", "alkohol",
     "amputace", "ananas", "andulka", "anekdota", "anketa", "antika", "anulovat", "archa",
     "arogance", "asfalt", "asistent", "aspirace", "astma", "astronom", "atlas", "atletika",
@@ -1,5 +1,5 @@
 // English BIP39/SLIP39 wordlist
-pub const ENGLISH_WORDS: [&str; 2048] = [
+pub static ENGLISH_WORDS: [&str; 2048] = [
     "abandon", "ability", "able", "about", "above", "absent", "absorb", "abstract",
     "absurd", "abuse", "access", "accident", "account", "accuse", "achieve", "acid",
     "acoustic", "acquire", "across", "act", "action", "actor", "actress", "actual",
@@ -1,5 +1,5 @@
 // French BIP39/SLIP39 wordlist
-pub const FRENCH_WORDS: [&str; 2048] = [
+pub static FRENCH_WORDS: [&str; 2048] = [
     "abaisser", "abandon", "abdiquer", "abeille", "abolir", "aborder", "aboutir", "aboyer",
     "abrasif", "abreuver", "abriter", "abroger", "abrupt", "absence", "absolu", "absurde",
     "abusif", "abyssal", "académie", "acajou", "acarien", "accabler", "accepter", "acclamer",
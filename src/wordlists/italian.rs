@@ -1,5 +1,5 @@
 // Italian BIP39/SLIP39 wordlist
-pub const ITALIAN_WORDS: [&str; 2048] = [
+pub static ITALIAN_WORDS: [&str; 2048] = [
     "abaco", "abbaglio", "abbinato", "abete", "abisso", "abolire", "abrasivo", "abrogato",
     "accadere", "accenno", "accusato", "acetone", "achille", "acido", "acqua", "acre",
     "acrilico", "acrobata", "acuto", "adagio", "addebito", "addome", "adeguato", "aderire",
@@ -1,5 +1,5 @@
 // Japanese BIP39/SLIP39 wordlist
-pub const JAPANESE_WORDS: [&str; 2048] = [
+pub static JAPANESE_WORDS: [&str; 2048] = [
     "あいこくしん", "あいさつ", "あいだ", "あおぞら", "あかちゃん", "あきる", "あけがた", "あける",
     "あこがれる", "あさい", "あさひ", "あしあと", "あじわう", "あずかる", "あずき", "あそぶ",
     "あたえる", "あたためる", "あたりまえ", "あたる", "あつい", "あつかう", "あっしゅく", "あつまり",
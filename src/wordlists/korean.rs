@@ -1,5 +1,5 @@
 // Korean BIP39/SLIP39 wordlist
-pub const KOREAN_WORDS: [&str; 2048] = [
+pub static KOREAN_WORDS: [&str; 2048] = [
     "가격", "가끔", "가난", "가능", "가득", "가르침", "가뭄", "가방",
     "가상", "가슴", "가운데", "가을", "가이드", "가입", "가장", "가정",
     "가족", "가죽", "각오", "각자", "간격", "간부", "간섭", "간장",
@@ -1,4 +1,6 @@
 // Wordlist modules for different languages
+// the lists are statics, not consts: a const array is copied wherever it is indexed with a runtime index
+// (clippy's large_const_arrays, which fails the -D warnings build)
 pub mod slip39;
 pub mod english;
 pub mod czech;
@@ -10,6 +12,7 @@ pub mod japanese;
 pub mod korean;
 pub mod chinese_simplified;
 pub mod chinese_traditional;
+pub mod pinyin;
 
 use slip39::SLIP39_WORDS;
 use english::ENGLISH_WORDS;
@@ -24,7 +27,7 @@ use chinese_simplified::CHINESE_SIMPLIFIED_WORDS;
 use chinese_traditional::CHINESE_TRADITIONAL_WORDS;
 
 // Re-export the words array for compatibility with existing code
-pub const WORDS: [[&str; 2048]; 11] = [
+pub static WORDS: [[&str; 2048]; 11] = [
     SLIP39_WORDS,
     ENGLISH_WORDS,
     CZECH_WORDS,
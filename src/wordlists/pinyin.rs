@@ -0,0 +1,267 @@
+// Pinyin readings of the Chinese BIP39 wordlists (the simplified and traditional lists have the same order)
+// numbered tones (5 is the neutral tone), v for ü, the most common reading first
+// written for this program from the standard Mandarin (Putonghua) readings, then the other common readings of the character;
+// checked against the Unicode::Collate::CJK::Pinyin table of Perl (the CLDR pinyin order, each character filed under one reading):
+// every character is filed under the syllable of one of its readings here, except 呵 (a1 there, he1 is the usual reading)
+// and 佛 (fu2 there, as in 彷佛), and the traditional 矽 (xi4 in Taiwan, see below) where the simplified list has 硅 (gui1)
+pub static PINYIN_READINGS: [&str; 2048] = [
+    "de5 di4 di2", "yi1", "shi4", "zai4", "bu4", "le5 liao3", "you3", "he2",
+    "ren2", "zhe4", "zhong1", "da4", "wei4 wei2", "shang4", "ge4", "guo2",
+    "wo3", "yi3", "yao4 yao1", "ta1", "shi2", "lai2", "yong4", "men5",
+    "sheng1", "dao4", "zuo4", "di4 de5", "yu2", "chu1", "jiu4", "fen1 fen4",
+    "dui4", "cheng2", "hui4", "ke3", "zhu3", "fa1 fa4", "nian2", "dong4",
+    "tong2", "gong1", "ye3", "neng2", "xia4", "guo4", "zi3", "shuo1",
+    "chan3", "zhong3 zhong4", "mian4", "er2", "fang1", "hou4", "duo1", "ding4",
+    "xing2 hang2", "xue2", "fa3", "suo3", "min2", "de2 dei3", "jing1", "shi2",
+    "san1", "zhi1", "jin4", "zhe5 zhao2 zhuo2", "deng3", "bu4", "du4", "jia1",
+    "dian4", "li4", "li3", "ru2", "shui3", "hua4", "gao1", "zi4",
+    "er4", "li3", "qi3", "xiao3", "wu4", "xian4", "shi2", "jia1",
+    "liang4 liang2", "dou1 du1", "liang3", "ti3", "zhi4", "ji1", "dang1", "shi3",
+    "dian3", "cong2", "ye4", "ben3", "qu4", "ba3", "xing4", "hao3 hao4",
+    "ying1 ying4", "kai1", "ta1", "he2", "hai2 huan2", "yin1", "you2", "qi2",
+    "xie1", "ran2", "qian2", "wai4", "tian1", "zheng4", "si4", "ri4",
+    "na4", "she4", "yi4", "shi4", "ping2", "xing2", "xiang1", "quan2",
+    "biao3", "jian1", "yang4", "yu3", "guan1", "ge4", "zhong4 chong2", "xin1",
+    "xian4", "nei4", "shu4 shu3", "zheng4", "xin1", "fan3", "ni3", "ming2",
+    "kan4", "yuan2", "you4", "me5", "li4", "bi3", "huo4", "dan4",
+    "zhi4", "qi4", "di4", "xiang4", "dao4", "ming4", "ci3", "bian4",
+    "tiao2", "zhi3 zhi1", "mei2", "jie2", "jie3", "wen4", "yi4", "jian4",
+    "yue4", "gong1", "wu2", "xi4", "jun1", "hen3", "qing2", "zhe3",
+    "zui4", "li4", "dai4", "xiang3", "yi3", "tong1", "bing4", "ti2",
+    "zhi2", "ti2", "dang3", "cheng2", "zhan3", "wu3", "guo3", "liao4",
+    "xiang4", "yuan2", "ge2", "wei4", "ru4", "chang2", "wen2", "zong3",
+    "ci4", "pin3", "shi4", "huo2", "she4", "ji2", "guan3", "te4",
+    "jian4", "zhang3 chang2", "qiu2", "lao3", "tou2", "ji1", "zi1", "bian1",
+    "liu2", "lu4", "ji2", "shao3 shao4", "tu2", "shan1", "tong3", "jie1",
+    "zhi1", "jiao4", "jiang1", "zu3", "jian4", "ji4", "bie2", "ta1",
+    "shou3", "jiao3", "qi1", "gen1", "lun4", "yun4", "nong2", "zhi3",
+    "ji3 ji1", "jiu3", "qu1", "qiang2 qiang3", "fang4", "jue2", "xi1", "bei4",
+    "gan4 gan1", "zuo4", "bi4", "zhan4", "xian1", "hui2", "ze2", "ren4",
+    "qu3", "ju4", "chu4 chu3", "dui4", "nan2", "gei3 ji3", "se4", "guang1",
+    "men2", "ji2", "bao3", "zhi4", "bei3", "zao4", "bai3", "gui1",
+    "re4", "ling3", "qi1", "hai3", "kou3", "dong1", "dao3", "qi4",
+    "ya1", "zhi4", "shi4", "jin1", "zeng1", "zheng1", "ji4", "jie1",
+    "you2", "si1", "shu4", "ji2", "jiao1", "shou4", "lian2", "shen2 shi2",
+    "ren4", "liu4", "gong4", "quan2", "shou1", "zheng4", "gai3", "qing1",
+    "mei3", "zai4", "cai3", "zhuan3 zhuan4", "geng4 geng1", "dan1", "feng1", "qie1 qie4",
+    "da3", "bai2", "jiao4 jiao1", "su4", "hua1", "dai4", "an1", "chang3",
+    "shen1", "che1", "li4", "zhen1", "wu4", "ju4", "wan4", "mei3",
+    "mu4", "zhi4", "da2", "zou3", "ji1", "shi4", "yi4", "sheng1",
+    "bao4", "dou4 dou3", "wan2", "lei4", "ba1", "li2", "hua2", "ming2",
+    "que4", "cai2", "ke1", "zhang1", "xin4", "ma3", "jie2", "hua4",
+    "mi3", "zheng3", "kong1 kong4", "yuan2", "kuang4", "jin1", "ji2", "wen1",
+    "chuan2", "tu3", "xu3", "bu4", "qun2", "guang3", "shi2", "ji4",
+    "xu1", "duan4", "yan2", "jie4", "la1", "lin2", "lv4", "jiao4",
+    "qie3", "jiu1", "guan1", "yue4", "zhi1", "zhuang1", "ying3", "suan4",
+    "di1", "chi2", "yin1", "zhong4", "shu1", "bu4", "fu4", "rong2",
+    "er2", "xu1", "ji4", "shang1", "fei1", "yan4", "lian2", "duan4",
+    "shen1", "nan2", "jin4", "kuang4", "qian1", "zhou1", "wei3", "su4",
+    "ji4", "bei4", "ban4", "ban4", "qing1", "sheng3", "lie4", "xi2",
+    "xiang3", "yue1", "zhi1", "ban1", "shi3", "gan3", "lao2", "bian4 pian2",
+    "tuan2", "wang3", "suan1", "li4", "shi4", "ke4", "he2", "chu2",
+    "xiao1", "gou4", "fu3", "cheng1", "tai4", "zhun3", "jing1", "zhi2",
+    "hao4", "lv4 shuai4", "zu2", "wei2", "hua4 hua2", "xuan3", "biao1", "xie3",
+    "cun2", "hou4", "mao2", "qin1", "kuai4", "xiao4", "si1", "yuan4",
+    "cha2", "jiang1", "xing2", "yan3", "wang2", "an4", "ge2", "yang3",
+    "yi4", "zhi4", "pai4", "ceng2", "pian4", "shi3", "que4", "zhuan1",
+    "zhuang4", "yu4", "chang3", "jing1", "shi2", "shi4", "shu3", "yuan2",
+    "bao1", "huo3", "zhu4", "diao4 tiao2", "man3", "xian4", "ju2", "zhao4",
+    "can1 shen1", "hong2", "xi4", "yin3", "ting1", "gai1", "tie3", "jia4",
+    "yan2", "shou3", "di3", "ye4", "guan1", "de2", "sui2", "bing4",
+    "su1", "shi1", "er3", "si3", "jiang3", "pei4", "nv3", "huang2",
+    "tui1", "xian3", "tan2", "zui4", "shen2", "yi4", "ne5", "xi2",
+    "han2", "qi3", "wang4", "mi4", "pi1", "ying2", "xiang4", "fang2",
+    "ju3", "qiu2", "ying1", "yang3", "shi4", "gao4", "li3", "tai2",
+    "luo4", "mu4", "bang1", "lun2", "po4", "ya4", "shi1", "wei2",
+    "zhu4", "yuan3", "zi4", "cai2", "pai2", "gong1 gong4", "he2", "tai4",
+    "feng1", "ling4", "shi1", "jian3", "shu4", "rong2", "zen3", "zhi3",
+    "an4", "yan2", "shi4", "jun1", "wu3", "gu4", "ye4", "yu2",
+    "bo1", "shi4", "jin3", "fei4", "jin3", "ai4", "zuo3", "zhang1",
+    "zao3", "chao2 zhao1", "hai4", "xu4", "qing1", "fu2", "shi4", "shi2",
+    "chong1", "bing1", "yuan2", "pan4", "hu4", "si1", "zu2", "mou3",
+    "lian4", "cha1 cha4 chai1", "zhi4", "ban3", "tian2", "jiang4 xiang2", "hei1", "fan4",
+    "fu4", "ji1", "fan4", "ji4", "xing4 xing1", "si4 shi4", "yu2", "jian1",
+    "qu1 qu3", "shu1", "xiu1", "gu4", "cheng2", "fu1", "gou4", "song4",
+    "bi3", "chuan2", "zhan4", "you4", "cai2", "chi1", "fu4", "chun1",
+    "zhi2", "jue2 jiao4", "han4", "hua4", "gong1", "ba1", "gen1", "sui1",
+    "za2", "fei1", "jian3", "xi1", "zhu4", "sheng1", "yang2", "hu4",
+    "chu1", "chuang4", "kang4", "kao3", "tou2", "huai4", "ce4", "gu3",
+    "jing4", "huan4", "wei4", "pao3", "liu2", "gang1", "ceng2 zeng1", "duan1",
+    "ze2", "zhan4", "jian3", "shu4", "qian2", "fu4", "jin4 jin3", "di4",
+    "she4", "cao3", "chong1", "cheng2", "du2", "ling4", "xian4", "a1",
+    "xuan1", "huan2", "shuang1", "qing3", "chao1", "wei1", "rang4", "kong4",
+    "zhou1", "liang2", "zhou2", "zhao3", "fou3", "ji4", "yi4", "yi1",
+    "you1", "ding3", "chu3", "zai4 zai3", "dao3 dao4", "fang2", "tu1", "zuo4",
+    "fen3", "di2", "lve4", "ke4", "yuan2", "leng3", "sheng4", "jue2",
+    "xi1", "kuai4", "ji4", "ce4", "si1", "xie2", "su4", "nian4",
+    "chen2", "reng2", "luo2", "yan2", "you3", "yang2", "cuo4", "ku3",
+    "ye4", "xing2", "yi2", "pin2", "zhu2", "kao4", "hun4", "mu3",
+    "duan3", "pi2", "zhong1", "ju4", "qi4", "cun1", "yun2", "na3",
+    "ji4", "ju4", "wei4", "ting2", "lie4", "yang1", "cha2", "shao1",
+    "xun4", "jing4", "ruo4", "yin4", "zhou1", "ke4", "kuo4", "ji1",
+    "kong3", "gao3", "shen4", "shi4", "dai4", "he2", "xiao4", "san4 san3",
+    "qin1", "ba5", "jia3", "you2", "jiu3", "cai4", "wei4", "jiu4",
+    "mo2", "hu2", "huo4", "sun3", "yu4", "zu3", "hao2", "pu3",
+    "wen3", "yi3", "ma1", "zhi2", "xi1", "kuo4", "yin2", "yu3",
+    "hui1", "jiu3", "shou3", "na2", "xu4", "zhi3", "yi1", "que1",
+    "yu3", "ma5", "zhen1", "liu2", "a1", "ji2", "chang4", "wu4",
+    "xun4", "yuan4", "shen3", "fu4", "huo4", "cha2", "xian1", "liang2",
+    "jin1", "hai2", "tuo1", "liu2", "fei2", "shan4", "long2", "yan3",
+    "fu4", "jian4", "xue4 xie3", "huan1", "xie4", "zhang3", "ge1", "sha1",
+    "gang1", "gong1", "wei4", "dun4", "tao3", "wan3", "li4", "luan4",
+    "ran2", "mao2", "hu1", "sha1", "yao4", "ning2", "lu3", "gui4",
+    "zhong1", "mei2", "du2", "ban1", "bo2", "xiang1", "jie4", "po4",
+    "ju4", "feng1", "pei2", "wo4", "lan2", "dan1", "xian2", "dan4",
+    "chen2", "jia3 jia4", "chuan1", "zhi2", "da2", "le4 yue4", "shei2 shui2", "shun4",
+    "yan1", "suo1", "zheng1", "lian3", "xi3", "song1", "jiao3", "kun4",
+    "yi4", "mian3", "bei4 bei1", "xing1", "fu2", "mai3", "ran3", "jing3",
+    "gai4", "man4", "pa4", "ci2", "bei4", "zu3", "huang2", "cu4",
+    "jing4", "bu3", "ping2", "fan1", "rou4", "jian4", "ni2", "yi1",
+    "kuan1", "yang2", "mian2", "xi1", "shang1", "cao1", "chui2", "qiu1",
+    "yi2", "qing1", "tao4", "du1", "zhen4", "jia4", "liang4", "mo4",
+    "xian4", "qing4", "bian1", "niu2", "chu4", "ying4", "lei2", "xiao1",
+    "shi1", "zuo4", "ju1", "zhua1", "lie4", "bao1", "hu1", "niang2",
+    "jing3", "wei1", "lv4", "jing1", "hou4", "meng2", "heng2", "ji1",
+    "sun1", "yan2", "wei1", "jiao1", "wu1", "xiang1", "lin2", "lu4",
+    "gu4", "diao4", "ya5", "deng1", "sui4", "cuo4", "shu4", "nai4",
+    "ju4", "yu4", "zhao4", "tiao4", "ge1", "ji4", "ke4", "kai3",
+    "hu2", "e2", "kuan3", "shao4", "juan3", "qi2", "wei3", "zheng1",
+    "zhi2", "yong3", "zong1", "miao2", "chuan1", "lu2", "yan2", "ruo4",
+    "ling2", "yang2", "zou4", "yan2", "lu4", "gan3 gan1", "tan4", "hua2",
+    "zhen4", "fan4", "nong2", "hang2", "huai2", "gan3", "ku4", "duo2",
+    "yi1", "ling2", "shui4", "tu2", "mie4", "sai4", "gui1", "zhao4",
+    "gu3", "bo1", "pan2", "cai2", "xian3", "kang1", "wei2", "lu4",
+    "jun1 jun4", "chun2", "jie4", "tang2", "gai4", "heng2", "fu2", "si1",
+    "nu3", "tang2", "yu4", "qiang1", "run4", "fu2", "ha1", "jing4",
+    "shu2", "chong2", "ze2", "nao3", "rang3", "tan4", "ou1", "bian4",
+    "ce4", "zhai4", "gan3", "che4", "lv4", "xie2", "bao2 bo2", "ting2",
+    "na4", "dan4 tan2", "si4", "shen1", "zhe2", "mai4", "shi1", "an4",
+    "he2", "wa3", "sai1 se4 sai4", "chuang2", "zhu4", "e4", "hu4", "fang3",
+    "ta3", "qi2", "tou4", "liang2", "dao1", "xuan2", "ji4", "ka3",
+    "lv4", "yu4", "fen4", "du2", "ni2", "tui4", "xi3", "bai3",
+    "hui1", "cai3", "mai4", "hao4", "xia4", "ze2", "mang2", "tong2",
+    "xian4", "ying4", "yu3", "fan2", "quan1", "xue3", "han2", "yi4",
+    "chou1", "pian1", "zhen4", "yin1", "ding1", "chi3", "zhui1", "dui1",
+    "xiong2", "ying2", "fan4", "ba4", "lou2", "bi4", "mou2", "dun1",
+    "ye3", "zhu1", "qi2", "lei4 lei3", "pian1", "dian3", "guan3", "suo3",
+    "qin2", "zhi1", "chao2", "ye2", "dou4", "hu1", "tuo1", "jing1",
+    "su4", "yi2", "yu4", "zhu1", "ti4", "xian1", "cu1", "qing1",
+    "shang4", "tong4", "chu3", "xie4", "fen4", "gou4", "mo2", "jun1",
+    "chi2", "pang2", "sui4", "gu3", "jian1", "bu3", "di4", "bao4",
+    "ge1", "guan4", "shu1", "shi4", "ci2", "wang2", "bi4", "dun4",
+    "bao3", "wu3", "chen2", "wen2", "jie1", "pao4", "can2", "dong1",
+    "qiao2", "fu4", "jing3", "zong1", "zhao1", "wu2", "fu4", "fu2",
+    "zao1", "xu2", "nin2", "yao2", "gu3", "zan4", "xiang1", "ge2",
+    "ding4", "nan2", "chui1", "yuan2", "fen1", "tang2", "bai4", "song4",
+    "bo1", "ju4", "geng1", "tan3", "rong2", "bi4", "wan1", "jian4",
+    "fan2", "zhu4", "guo1", "jiu4", "en1", "bo1 bao1", "ning2", "jian3",
+    "chi3", "jie2", "lian4", "ma2", "fang3", "jin4", "fei4", "sheng4",
+    "ban3", "huan3", "jing4", "jing1", "chang1", "hun1", "she4", "tong3",
+    "zui3", "cha1", "an4", "lang3", "zhuang1", "jie1", "cang2 zang4", "gu1",
+    "mao4", "fu3", "nu2", "la5", "guan4", "cheng2", "huo3", "hui1",
+    "yun2", "sha1", "zha1", "bian4", "er3", "biao1", "chen2", "yi4",
+    "li2", "di3", "mai4", "xiu4", "sa4", "e2", "wang3", "wu3",
+    "dian4", "pen1", "zong4", "cun4", "han4", "gua4", "hong2", "he4",
+    "shan3", "jian3", "bao4", "xi1", "jin1", "dao4", "qiang2", "ruan3",
+    "yong3", "xiang4", "gun3", "li2", "meng2", "fang1", "ken3", "po1",
+    "zhu4", "dang4", "tui3", "yi2", "lv3", "wei3", "ya4 zha2", "bing1",
+    "gong4", "deng1", "li2", "xue1 xiao1", "zuan1 zuan4", "le4", "tao2", "zhang4",
+    "an1", "guo1", "feng1", "bi4", "gang3", "fu2", "gui3", "mu3",
+    "bi4", "ca1", "mo4", "ci4", "lang4", "mi4", "yuan2", "zhu1",
+    "jian4", "shou4", "gu3", "dao3", "gan1", "pao4", "shui4", "tong2",
+    "zhu4", "tang1", "fa2", "xiu1", "hui4", "she3 she4", "mu4", "rao4",
+    "zha4", "zhe2", "lin2", "ji4", "peng2", "dan4", "jian1", "qi3",
+    "xian4", "chai2", "cheng2", "tu2", "yan2", "lei4", "shao1", "wang4",
+    "beng4", "lan2", "tuo1", "dong4", "shou4", "jing4", "xin1", "zhuang4",
+    "feng1", "pin2", "xu1", "wan1", "mo2", "tai4", "you4", "ting2",
+    "zun1", "chuang1", "gang1", "nong4", "li4", "yi2", "shi4", "gong1",
+    "jie3", "zhen4", "rui4", "guai4", "you2", "qin2", "xun2", "miao2",
+    "mo2", "wei2", "jia1", "yao1", "yuan2", "zhu1", "qiong2", "sen1",
+    "zhi1", "zhu2", "gou1", "cui1", "sheng2", "yi4", "bang1", "sheng4",
+    "xing4", "jiang1", "lan2", "yong1", "ya2", "zhu4", "li3", "lv4",
+    "na4", "wen2", "ba4", "pai1", "zan2", "han3", "xiu4", "ai1",
+    "qin2", "fa2", "jiao1", "qian2", "wu3", "mo4", "yu4", "feng2 feng4",
+    "xing4", "kan1", "bao3", "fang3", "jiang3", "lv3", "gui3", "li4",
+    "kua4", "mo4", "wa1", "lian4", "sao3", "he1", "dai4", "tan4",
+    "wu1", "mu4", "zhu1", "hu2", "li4", "mei2", "nai3", "jie2",
+    "zai1", "zhou1", "jian4", "ben3", "song4", "bao4", "hui3", "dong3",
+    "han2", "zhi4", "bu4 pu3", "ji4", "jie4", "yue4", "du4", "tiao1 tiao3",
+    "dan1", "jian1", "bei4", "peng4", "ba2", "die1", "dai4", "ma3",
+    "meng4", "ya2", "rong2", "chi4", "yu2", "ku1", "jing4", "ke1",
+    "ben1", "qian1", "zhong4", "hu3", "xi1", "mei4", "fa2", "zhen1",
+    "shen1", "zhuo1", "zun1", "yun3", "long2", "luo2", "cang1", "wei4",
+    "rui4", "xiao3", "dan4", "jian1", "yin3", "ai4", "he4", "bo1",
+    "zhong1", "su4", "gang1", "qian1", "qiang3", "bo2", "qiao3", "ke2 qiao4",
+    "xiong1", "du4", "xun4", "cheng2", "bi4", "xiang2", "ke1", "ye4",
+    "xun2", "ju3", "bei1", "guan4", "ling2", "lun2", "piao4", "xun2",
+    "gui4", "pu1 pu4", "sheng4", "kong3", "qia4", "zheng4", "qu4", "tai2",
+    "huang1", "teng2", "tie1", "rou2", "di1", "meng3", "kuo4", "liang4",
+    "qi1", "tian2", "che4", "chu3", "qian1", "nao4", "rao3", "zi3",
+    "sha1", "di4", "xi4", "diao4", "tao2", "fa2", "wei4", "liao2",
+    "ping2", "po2", "fu3", "bi4", "mo1", "ren3", "xia1", "la4",
+    "lin2", "xiong1", "gong3", "ji3", "ou3", "qi4", "cao2", "jin4",
+    "ru3", "deng4", "ji2", "ren2", "lan4", "zhuan1", "zu1", "wu1",
+    "jian4", "ban4", "gua1", "qian3", "bing3", "zan4", "zao4", "xiang4",
+    "liu3", "mi2", "nuan3", "pai2", "yang1", "dan3", "xiang2", "huang2",
+    "ta4", "ci2", "pu3", "dai1", "bin1", "hu2", "luo4", "hui1",
+    "fen4", "jing4", "xi4", "nu4", "zhan1 nian2", "nai3", "xu4", "jian1",
+    "ji2", "min3", "tu2", "xi1", "jie1", "zhen1", "xuan2", "jue2",
+    "xiang3", "jiu1", "xing3", "kuang2", "suo3", "dian4", "hen4", "sheng1",
+    "ba4", "pa2", "shang3", "ni4", "wan2", "ling2", "zhu4", "miao3",
+    "zhe4", "mao4", "yi4", "bi3", "xi1", "ya1", "qu1", "feng4",
+    "chen2", "chu4 xu4", "bei4", "zhi4", "luan3", "shu3", "ti1", "yan2",
+    "tan1", "qi2", "qu1", "shai1", "xia2", "mao4", "sha2", "shou4",
+    "yi4", "jin4", "quan2", "mao4", "chi2", "gui1", "jiang1", "dai4",
+    "lou4", "gao3", "guan1 guan4", "nen4", "xie2", "xin1", "lao2", "pan4",
+    "shi2", "ao4", "ming2", "ling3", "yang2", "ping2", "chuan4", "tang2",
+    "hui4", "jiao4", "rong2", "pen2", "xi1", "miao4", "chou2", "dong4",
+    "fu3", "she4", "xi2", "jin1", "ju4", "liao2", "han4", "jia3",
+    "niao3", "qi1", "shen3 chen2", "mei2", "shu1", "tian1", "bang4", "sui4",
+    "xiao1", "han2", "bi1", "niu3", "qiao2", "liang2", "ting3", "wan3",
+    "zai1", "chao3", "bei1", "huan4", "liu2", "quan4", "hao2", "liao2",
+    "bo2", "hong2", "dan4", "li4", "bai4", "gou3", "mai2", "gun3",
+    "yan3", "yin3", "ban1", "ma4", "ci2", "gou1", "kou4", "gu1",
+    "jiang3", "rong2", "wu4", "zhang4", "duo3", "mu3", "ni3", "yu3",
+    "ji2", "shan3", "diao1", "chang2", "xu4", "chong2", "jian3", "chang4",
+    "ting1", "yao3", "shi3", "shu3", "shua1", "chi4", "fan1", "fu4",
+    "feng4", "fo2", "jiao1", "man4", "man4", "shan4", "gai4", "tao2",
+    "fu2", "zi3", "fan3", "su2", "kui1", "qiang1", "xie2", "leng2",
+    "fu4", "kuang4", "qiao1 qiao3", "shu1", "zhuang4", "pian4", "kan1", "wang4",
+    "fei4", "gu1", "tu3 tu4", "meng4", "qu2", "qu1", "ji2", "miao4",
+    "xi1", "yang3", "hen3", "zhang4", "xie2", "pao1", "mei2", "sang1",
+    "gang3", "ma5", "shuai1", "dao4", "shen4", "zang1 zang4", "lai4", "yong3",
+    "tian2", "cao2", "yue4", "ji1", "li5", "li4", "ting1", "wei3",
+    "yi4", "zuo2", "wei3", "zheng4", "zhu3", "tan4", "ding1", "da1",
+    "jing1", "long2", "ku4", "tou1", "gong1", "zhui1", "heng2", "jie2",
+    "keng1", "bi2", "yi4", "lun2", "xu4", "yu4", "dai3 dai4", "guan4",
+    "luo4", "peng2", "yi4", "peng2", "shu1", "si4", "zhou4", "mu4",
+    "ye3", "ku1", "ce4", "shi1", "tu1", "shen1", "pi1", "xi1",
+    "yan4", "hong1", "xin1", "jin4", "shou4", "yu4", "ding4", "jin3",
+    "sang4 sang1", "xun2", "duan4", "long3", "sou1", "pu1", "yao1", "ting2",
+    "zhi3", "mai4", "shu1", "cui4", "mei2", "xian2", "you1", "fen1",
+    "wan2", "yu3", "zhang3", "xie4", "zhang4", "pei2", "pi4 bi4", "cheng2",
+    "hang2", "yao2", "du4", "zhuo1", "piao1", "piao1 piao4", "kun1", "qi1",
+    "wu2", "lang2", "wan2", "zhi1", "he1", "shi4", "xiao1", "ya3",
+    "you2", "qian1", "yan4", "sa1 sa3", "yin1", "fu4", "yan4", "fan2",
+    "zhai4", "zhang4", "ban1", "ling2", "zhi3", "chun2", "dong3", "bing3",
+    "chu2", "zi1", "ban4", "fu4", "fu4", "tuo3", "rou2", "xian2",
+    "chai1", "wai1", "pu2", "an4", "diu1", "hao4", "hui1", "ang2",
+    "dian4", "dang3", "lan3", "tan1", "wei4", "jiao3", "wang1", "huang1",
+    "feng2", "nuo4", "jiang1", "yi4", "xiong1", "lie4", "wu1", "yao4",
+    "hun1", "tang3", "ying2", "qi2", "qiao2", "xi1", "cong2", "lu2",
+    "mo3", "men4", "zi1", "gua1", "jia4", "lan3", "wu4", "zhai1",
+    "er3", "zhi4", "po1", "huan4", "bing3", "hui4", "can3", "jia1",
+    "chou2", "la4", "wo1", "di2", "jian4", "qiao2", "bao3", "po1",
+    "cong1", "zhao4", "huo4", "lao1", "tai1", "cang1", "bin1", "liang3 lia3",
+    "tong3", "xiang1", "kan3", "xia2", "shao4", "tao2", "feng1", "huai2",
+    "sui4", "xiong2", "fen4", "hong1", "su4", "dang4", "ge1", "bo2",
+    "sao3", "yu4", "xi3", "jian4", "juan1", "chang2", "cheng1", "shai4",
+    "bian4", "dian4", "lian2", "tan1", "jiao3", "jiang4", "ping2", "yi4",
+    "ai1", "cai4", "du3", "mo4", "zhou4", "chang4", "die2", "ge2",
+    "lai2", "qiao1", "xia2", "gou1", "hen2", "ba4", "xiang4", "e4",
+    "huo4", "qiu1", "xuan2", "liu1", "yue1", "luo2", "peng2", "chang2",
+    "qing1", "fang2", "ting3", "tun1", "wei2", "yuan4", "ai3", "xie1",
+];
+
+// The readings of the traditional words that are not read like the simplified word at the same index
+pub static TRADITIONAL_READINGS: [(usize, &str); 1] = [(1653, "xi4 xi1")];
@@ -1,5 +1,5 @@
 // Portuguese BIP39/SLIP39 wordlist
-pub const PORTUGUESE_WORDS: [&str; 2048] = [
+pub static PORTUGUESE_WORDS: [&str; 2048] = [
     "abacate", "abaixo", "abalar", "abater", "abduzir", "abelha", "aberto", "abismo",
     "abotoar", "abranger", "abreviar", "abrigar", "abrupto", "absinto", "absoluto", "absurdo",
     "abutre", "acabado", "acalmar", "acampar", "acanhar", "acaso", "aceitar", "acelerar",
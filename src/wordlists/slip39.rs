@@ -1,5 +1,5 @@
 // SLIP39 wordlist (1024 words, used by Trezor)
-pub const SLIP39_WORDS: [&str; 2048] = [
+pub static SLIP39_WORDS: [&str; 2048] = [
     "academic", "acid", "acne", "acquire", "acrobat", "activity", "actress", "adapt", 
     "adequate", "adjust", "admit", "adorn", "adult", "advance", "advocate", "afraid", 
     "again", "agency", "agree", "aide", "aircraft", "airline", "airport", "ajar", 
@@ -1,5 +1,5 @@
 // Spanish BIP39/SLIP39 wordlist
-pub const SPANISH_WORDS: [&str; 2048] = [
+pub static SPANISH_WORDS: [&str; 2048] = [
     "ábaco", "abdomen", "abeja", "abierto", "abogado", "abono", "aborto", "abrazo",
     "abrir", "abuelo", "abuso", "acabar", "academia", "acceso", "acción", "aceite",
     "acelga", "acento", "aceptar", "ácido", "aclarar", "acné", "acoger", "acoso",